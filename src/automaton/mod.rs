@@ -0,0 +1,219 @@
+//! Contains the [`Automaton`] struct, which runs multi-state rules such as Wireworld.
+
+mod rule;
+
+use crate::game::panic;
+use alloc::{vec, vec::Vec};
+use core::ops::{Index, IndexMut};
+pub use rule::*;
+
+/// A multi-state cellular automaton, driven by a [`RuleTree`].
+///
+/// Like [`Game`](crate::Game), it wraps around at the edges. Cells are stored as states from `0`
+/// to [`RuleTree::states`], where `0` is usually the background. Setting a cell to a state the
+/// rule doesn't have is a logic error, and [`tick`](Automaton::tick) may panic or produce
+/// meaningless states.
+///
+/// # Examples
+/// ```
+/// # use cgol::automaton::{Automaton, RuleTree};
+/// let mut wireworld = Automaton::new(6, 3, RuleTree::wireworld());
+///
+/// // an electron moving along a wire
+/// wireworld.get_row_mut(1).copy_from_slice(&[2, 1, 3, 3, 3, 3]);
+///
+/// wireworld.tick();
+/// assert_eq!(wireworld.get_row(1), &[3, 2, 1, 3, 3, 3]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Automaton {
+    cells: Vec<u8>,
+    next: Vec<u8>,
+    width: usize,
+    height: usize,
+    rule: RuleTree,
+}
+
+impl Automaton {
+    /// Creates a new automaton, with every cell in state `0`.
+    ///
+    /// # Panics
+    /// Panics if `width < 1`, `height < 1`, or if `width * height > isize::MAX`.
+    pub fn new(width: usize, height: usize, rule: RuleTree) -> Automaton {
+        if width == 0 {
+            panic::width_is_zero();
+        } else if height == 0 {
+            panic::height_is_zero();
+        }
+
+        // doesn't need documentation since it's already > isize::MAX
+        let area = width.checked_mul(height).expect("width * height overflow");
+        let cells = vec![0; area];
+        let next = cells.clone();
+
+        Self {
+            cells,
+            next,
+            width,
+            height,
+            rule,
+        }
+    }
+
+    /// Ticks once.
+    pub fn tick(&mut self) {
+        let row_max = self.height - 1;
+        let col_max = self.width - 1;
+        let moore = self.rule.neighborhood() == Neighborhood::Moore;
+        let cells = &self.cells;
+
+        for row in 0..self.height {
+            let up = if row == 0 { row_max } else { row - 1 } * self.width;
+            let down = if row == row_max { 0 } else { row + 1 } * self.width;
+            let row = row * self.width;
+
+            for col in 0..self.width {
+                let left = if col == 0 { col_max } else { col - 1 };
+                let right = if col == col_max { 0 } else { col + 1 };
+
+                // see Neighborhood::tree_order
+                let next = if moore {
+                    self.rule.eval(&[
+                        cells[up + left],
+                        cells[up + right],
+                        cells[down + left],
+                        cells[down + right],
+                        cells[up + col],
+                        cells[row + left],
+                        cells[row + right],
+                        cells[down + col],
+                        cells[row + col],
+                    ])
+                } else {
+                    self.rule.eval(&[
+                        cells[up + col],
+                        cells[row + left],
+                        cells[row + right],
+                        cells[down + col],
+                        cells[row + col],
+                    ])
+                };
+
+                self.next[row + col] = next;
+            }
+        }
+
+        core::mem::swap(&mut self.cells, &mut self.next);
+    }
+
+    /// Gets the rule this automaton is running.
+    #[inline]
+    pub fn rule(&self) -> &RuleTree {
+        &self.rule
+    }
+
+    /// Gets this automaton's width.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets this automaton's height.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets this automaton's area.
+    #[inline]
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Gets a specific cell, returning `None` if out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        if row < self.height && col < self.width {
+            Some(self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Mutably gets a specific cell, returning `None` if out of bounds.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut u8> {
+        if row < self.height && col < self.width {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Gets a slice of a specific row.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn get_row(&self, row: usize) -> &[u8] {
+        if row >= self.height {
+            panic::height(row, self.height);
+        }
+
+        let begin = row * self.width;
+        &self.cells[begin..begin + self.width]
+    }
+
+    /// Gets a mutable slice of a specific row.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn get_row_mut(&mut self, row: usize) -> &mut [u8] {
+        if row >= self.height {
+            panic::height(row, self.height);
+        }
+
+        let begin = row * self.width;
+        &mut self.cells[begin..begin + self.width]
+    }
+
+    /// Counts the cells in a specific state.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::automaton::{Automaton, RuleTree};
+    /// let wireworld = Automaton::new(10, 10, RuleTree::wireworld());
+    /// assert_eq!(wireworld.count(0), 100);
+    /// ```
+    pub fn count(&self, state: u8) -> usize {
+        self.cells.iter().filter(|&&c| c == state).count()
+    }
+
+    /// Sets every cell to state `0`.
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+}
+
+impl Index<(usize, usize)> for Automaton {
+    type Output = u8;
+
+    fn index(&self, (col, row): (usize, usize)) -> &u8 {
+        if row >= self.height {
+            panic::height(row, self.height);
+        } else if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Automaton {
+    fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut u8 {
+        if row >= self.height {
+            panic::height(row, self.height);
+        } else if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        &mut self.cells[row * self.width + col]
+    }
+}
@@ -0,0 +1,1005 @@
+//! Contains [`RuleTree`], which loads Golly `.rule` files.
+//!
+//! Wireworld and Langton's loops are built in. Any other rule, such as Codd's, can be loaded
+//! from the text of its `.rule` file with [`str::parse`], for example Golly's `Codd.rule`:
+//!
+//! ```no_run
+//! # use cgol::automaton::RuleTree;
+//! let text = std::fs::read_to_string("Codd.rule").unwrap();
+//! let codd: RuleTree = text.parse().unwrap();
+//! assert_eq!(codd.states(), 8);
+//! ```
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+/// The Wireworld rule, as a Golly `.rule` file.
+///
+/// States are `0` (empty), `1` (electron head), `2` (electron tail), and `3` (conductor).
+pub const WIREWORLD: &str = "\
+@RULE WireWorld
+
+@TABLE
+n_states:4
+neighborhood:Moore
+symmetries:rotate8
+
+var a={0,1,2,3}
+var b={0,1,2,3}
+var c={0,1,2,3}
+var d={0,1,2,3}
+var e={0,1,2,3}
+var f={0,1,2,3}
+var g={0,1,2,3}
+var h={0,1,2,3}
+var i={0,2,3}
+var j={0,2,3}
+var k={0,2,3}
+var l={0,2,3}
+var m={0,2,3}
+var n={0,2,3}
+var o={0,2,3}
+
+# electron head -> electron tail
+1,a,b,c,d,e,f,g,h,2
+# electron tail -> conductor
+2,a,b,c,d,e,f,g,h,3
+# conductor -> electron head, with exactly 1 or 2 electron head neighbors
+3,1,i,j,k,l,m,n,o,1
+3,1,1,j,k,l,m,n,o,1
+3,1,i,1,k,l,m,n,o,1
+3,1,i,j,1,l,m,n,o,1
+3,1,i,j,k,1,m,n,o,1
+";
+
+/// Langton's loops, as a Golly `.rule` file.
+///
+/// States are `0` (background), `1` (core), `2` (sheath), and `3` to `7` (signals). Each
+/// transition is written as the cell, its north, east, south and west neighbors, then its new
+/// state, using Langton's original table.
+pub const LANGTONS_LOOPS: &str = "\
+@RULE Langtons-Loops
+
+@TABLE
+n_states:8
+neighborhood:vonNeumann
+symmetries:rotate4
+
+0,0,0,0,0,0
+0,0,0,0,1,2
+0,0,0,0,2,0
+0,0,0,0,3,0
+0,0,0,0,5,0
+0,0,0,0,6,3
+0,0,0,0,7,1
+0,0,0,1,1,2
+0,0,0,1,2,2
+0,0,0,1,3,2
+0,0,0,2,1,2
+0,0,0,2,2,0
+0,0,0,2,3,0
+0,0,0,2,6,2
+0,0,0,2,7,2
+0,0,0,3,2,0
+0,0,0,5,2,5
+0,0,0,6,2,2
+0,0,0,7,2,2
+0,0,1,0,2,2
+0,0,1,1,2,0
+0,0,2,0,2,0
+0,0,2,0,3,0
+0,0,2,0,5,0
+0,0,2,1,2,5
+0,0,2,2,2,0
+0,0,2,3,2,2
+0,0,5,2,2,2
+0,1,2,3,2,1
+0,1,2,4,2,1
+0,1,2,5,2,5
+0,1,2,6,2,1
+0,1,2,7,2,1
+0,1,2,7,5,1
+0,1,4,2,2,1
+0,1,4,3,2,1
+0,1,4,4,2,1
+0,1,4,7,2,1
+0,1,6,2,5,1
+0,1,7,2,2,1
+0,1,7,2,5,5
+0,1,7,5,2,1
+0,1,7,6,2,1
+0,1,7,7,2,1
+0,2,5,2,7,1
+1,0,0,0,1,1
+1,0,0,0,6,1
+1,0,0,0,7,7
+1,0,0,1,1,1
+1,0,0,1,2,1
+1,0,0,2,1,1
+1,0,0,2,4,4
+1,0,0,2,7,7
+1,0,0,3,2,4
+1,0,0,5,1,1
+1,0,0,6,2,1
+1,0,1,2,1,1
+1,0,1,2,4,4
+1,0,1,2,7,7
+1,0,2,0,2,6
+1,0,2,1,2,1
+1,0,2,2,1,1
+1,0,2,2,4,4
+1,0,2,2,6,3
+1,0,2,2,7,7
+1,0,2,3,2,7
+1,0,2,4,2,4
+1,0,2,6,2,6
+1,0,2,6,4,4
+1,0,2,6,7,7
+1,0,2,7,1,0
+1,0,2,7,2,7
+1,0,5,4,2,7
+1,1,1,1,2,1
+1,1,1,2,2,1
+1,1,1,2,4,4
+1,1,1,2,5,1
+1,1,1,2,6,1
+1,1,1,2,7,7
+1,1,1,5,2,2
+1,1,2,1,2,1
+1,1,2,2,2,1
+1,1,2,2,4,4
+1,1,2,2,5,1
+1,1,2,2,7,7
+1,1,2,3,2,1
+1,1,2,4,2,4
+1,1,2,6,2,1
+1,1,2,7,2,7
+1,1,3,2,2,1
+1,2,2,2,4,4
+1,2,2,2,7,7
+1,2,2,4,3,4
+1,2,2,5,4,7
+1,2,3,2,4,4
+1,2,3,2,7,7
+1,2,4,2,5,5
+1,2,4,2,6,7
+1,2,5,2,7,5
+2,0,0,0,1,2
+2,0,0,0,2,2
+2,0,0,0,4,2
+2,0,0,0,7,1
+2,0,0,1,2,2
+2,0,0,1,5,2
+2,0,0,2,1,2
+2,0,0,2,2,2
+2,0,0,2,3,2
+2,0,0,2,4,2
+2,0,0,2,5,0
+2,0,0,2,6,2
+2,0,0,2,7,2
+2,0,0,3,2,6
+2,0,0,4,2,3
+2,0,0,5,1,7
+2,0,0,5,2,2
+2,0,0,5,7,5
+2,0,0,7,2,2
+2,0,1,0,2,2
+2,0,1,1,2,2
+2,0,1,2,2,2
+2,0,1,4,2,2
+2,0,1,7,2,2
+2,0,2,0,2,2
+2,0,2,0,3,2
+2,0,2,0,5,2
+2,0,2,0,7,3
+2,0,2,1,2,2
+2,0,2,1,5,2
+2,0,2,2,1,2
+2,0,2,2,2,2
+2,0,2,2,7,2
+2,0,2,3,2,1
+2,0,2,4,2,2
+2,0,2,4,5,2
+2,0,2,5,2,0
+2,0,2,5,5,2
+2,0,2,6,2,2
+2,0,2,7,2,2
+2,0,3,1,2,2
+2,0,3,2,1,6
+2,0,3,2,2,6
+2,0,3,4,2,2
+2,0,4,2,2,2
+2,0,5,1,2,2
+2,0,5,2,1,2
+2,0,5,2,2,2
+2,0,5,5,2,1
+2,0,5,7,2,5
+2,0,6,2,2,2
+2,0,6,7,2,2
+2,0,7,1,2,2
+2,0,7,2,2,2
+2,0,7,4,2,2
+2,0,7,7,2,2
+2,1,1,2,2,2
+2,1,1,2,6,1
+2,1,2,2,2,2
+2,1,2,2,4,2
+2,1,2,2,6,2
+2,1,2,2,7,2
+2,1,4,2,2,2
+2,1,5,2,2,2
+2,1,6,2,2,2
+2,1,7,2,2,2
+2,2,2,2,7,2
+2,2,2,4,4,2
+2,2,2,4,6,2
+2,2,2,7,6,2
+2,2,2,7,7,2
+3,0,0,0,1,3
+3,0,0,0,2,2
+3,0,0,0,4,1
+3,0,0,0,7,6
+3,0,0,1,2,3
+3,0,0,4,2,1
+3,0,0,6,2,2
+3,0,1,0,2,1
+3,0,1,2,2,0
+3,0,2,5,1,1
+4,0,1,1,2,0
+4,0,1,2,2,0
+4,0,1,2,5,0
+4,0,2,1,2,0
+4,0,2,2,2,1
+4,0,2,3,2,6
+4,0,2,5,2,0
+4,0,3,2,2,1
+5,0,0,0,2,2
+5,0,0,2,1,5
+5,0,0,2,2,5
+5,0,0,2,3,2
+5,0,0,2,7,2
+5,0,0,5,2,0
+5,0,2,0,2,2
+5,0,2,1,2,2
+5,0,2,1,5,2
+5,0,2,2,2,0
+5,0,2,2,4,4
+5,0,2,7,2,2
+5,1,2,1,2,2
+5,1,2,2,2,0
+5,1,2,4,2,2
+5,1,2,7,2,2
+6,0,0,0,1,1
+6,0,0,0,2,1
+6,0,2,1,2,0
+6,1,2,1,2,5
+6,1,2,1,3,1
+6,1,2,2,2,5
+7,0,0,0,7,7
+7,0,1,1,2,0
+7,0,1,2,2,0
+7,0,1,2,5,0
+7,0,2,1,2,0
+7,0,2,2,2,1
+7,0,2,2,5,1
+7,0,2,3,2,1
+7,0,2,5,2,5
+7,0,2,7,2,0
+";
+
+/// A set of states, which can hold every state from `0` to `255`.
+type Set = [u64; 4];
+
+fn set_contains(set: &Set, state: usize) -> bool {
+    set[state / 64] & 1 << (state % 64) != 0
+}
+
+fn set_insert(set: &mut Set, state: usize) {
+    set[state / 64] |= 1 << (state % 64);
+}
+
+fn set_of(state: usize) -> Set {
+    let mut set = [0; 4];
+    set_insert(&mut set, state);
+    set
+}
+
+/// The neighborhood used by a [`RuleTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Neighborhood {
+    /// The 4 orthogonal neighbors.
+    VonNeumann,
+    /// The 8 orthogonal and diagonal neighbors.
+    Moore,
+}
+
+impl Neighborhood {
+    /// The number of neighbors.
+    #[inline]
+    pub fn size(self) -> usize {
+        match self {
+            Neighborhood::VonNeumann => 4,
+            Neighborhood::Moore => 8,
+        }
+    }
+
+    /// For each input of the tree, the index of the table column it reads.
+    ///
+    /// Tables list the center, then the neighbors clockwise starting from north. Trees read
+    /// `NW, NE, SW, SE, N, W, E, S, C` or `N, W, E, S, C`, which is the order used by Golly.
+    fn tree_order(self) -> &'static [usize] {
+        match self {
+            Neighborhood::VonNeumann => &[1, 4, 2, 3, 0],
+            Neighborhood::Moore => &[8, 2, 6, 4, 1, 7, 3, 5, 0],
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`RuleTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuleTreeError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseRuleTreeError {
+    /// The line the error occured on, starting from `1`. This is `0` if the error isn't about a
+    /// specific line.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseRuleTreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            f.write_str(self.reason)
+        } else {
+            write!(f, "line {}: {}", self.line, self.reason)
+        }
+    }
+}
+
+fn error<T>(line: usize, reason: &'static str) -> Result<T, ParseRuleTreeError> {
+    Err(ParseRuleTreeError { line, reason })
+}
+
+/// A multi-state rule, stored as a decision tree.
+///
+/// It is created by parsing a Golly `.rule` file with [`str::parse`]. Both `@TABLE` and `@TREE`
+/// sections are supported, and tables are compiled into trees when they are parsed. Other
+/// sections (such as `@COLORS` and `@ICONS`) are ignored.
+///
+/// Tables support the `vonNeumann` and `Moore` neighborhoods, variables, and the `none`,
+/// `rotate4`, `rotate8`, `reflect_horizontal`, `rotate4reflect`, `rotate8reflect` and `permute`
+/// symmetries. Like in Golly, a variable which appears more than once in a transition must take
+/// the same value everywhere, and cells without a matching transition stay the same.
+///
+/// # Examples
+/// ```
+/// # use cgol::automaton::RuleTree;
+/// let rule: RuleTree = "
+/// @RULE Flip
+/// @TABLE
+/// n_states:2
+/// neighborhood:vonNeumann
+/// symmetries:none
+/// var a={0,1}
+/// 0,a,a,a,a,1
+/// 1,a,a,a,a,0
+/// ".parse().unwrap();
+///
+/// assert_eq!(rule.name(), Some("Flip"));
+/// assert_eq!(rule.states(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleTree {
+    name: Option<String>,
+    states: usize,
+    neighborhood: Neighborhood,
+    /// Flattened nodes, each taking up `states` entries. Entries are offsets of child nodes, or
+    /// the new state in the last level.
+    nodes: Vec<u32>,
+    root: u32,
+}
+
+impl RuleTree {
+    /// The Wireworld rule. See [`WIREWORLD`] for its states.
+    pub fn wireworld() -> RuleTree {
+        WIREWORLD.parse().expect("built-in rule should be valid")
+    }
+
+    /// Langton's loops. See [`LANGTONS_LOOPS`] for its states.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::automaton::{Automaton, RuleTree};
+    /// // Langton's original loop
+    /// let pattern = [
+    ///     "022222222000000",
+    ///     "217014014200000",
+    ///     "202222220200000",
+    ///     "272000021200000",
+    ///     "212000021200000",
+    ///     "202000021200000",
+    ///     "272000021200000",
+    ///     "212222221222220",
+    ///     "207107107111112",
+    ///     "022222222222220",
+    /// ];
+    ///
+    /// let mut loops = Automaton::new(40, 30, RuleTree::langtons_loops());
+    /// for (row, line) in pattern.iter().enumerate() {
+    ///     for (col, state) in line.bytes().enumerate() {
+    ///         loops[(col + 15, row + 10)] = state - b'0';
+    ///     }
+    /// }
+    /// assert_eq!(loops.count(2), 61);
+    ///
+    /// // it takes 151 generations to make a copy of itself
+    /// for _ in 0..151 {
+    ///     loops.tick();
+    /// }
+    /// assert_eq!(loops.count(2), 122);
+    /// ```
+    pub fn langtons_loops() -> RuleTree {
+        LANGTONS_LOOPS
+            .parse()
+            .expect("built-in rule should be valid")
+    }
+
+    /// Gets this rule's name, from its `@RULE` line.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the number of states.
+    #[inline]
+    pub fn states(&self) -> usize {
+        self.states
+    }
+
+    /// Gets the neighborhood this rule uses.
+    #[inline]
+    pub fn neighborhood(&self) -> Neighborhood {
+        self.neighborhood
+    }
+
+    /// Gets the next state of a cell, given its inputs in tree order (see
+    /// [`Neighborhood::tree_order`]).
+    #[inline]
+    pub(crate) fn eval(&self, inputs: &[u8]) -> u8 {
+        inputs.iter().fold(self.root, |node, &input| {
+            self.nodes[(node as usize) + input as usize]
+        }) as u8
+    }
+}
+
+impl FromStr for RuleTree {
+    type Err = ParseRuleTreeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        enum Section {
+            None,
+            Rule,
+            Table,
+            Tree,
+            Other,
+        }
+
+        let mut name = None;
+        let mut section = Section::None;
+        let mut table = Vec::new();
+        let mut tree = Vec::new();
+
+        for (i, line) in s.lines().enumerate() {
+            let number = i + 1;
+            let line = match line.find('#') {
+                Some(i) => &line[..i],
+                None => line,
+            }
+            .trim();
+
+            if line.is_empty() {
+                continue;
+            } else if let Some(rest) = line.strip_prefix('@') {
+                let mut words = rest.split_whitespace();
+                section = match words.next() {
+                    Some("RULE") => {
+                        name = words.next().map(String::from);
+                        Section::Rule
+                    }
+                    Some("TABLE") => Section::Table,
+                    Some("TREE") => Section::Tree,
+                    _ => Section::Other,
+                };
+                continue;
+            }
+
+            match section {
+                Section::Table => table.push((number, line)),
+                Section::Tree => tree.push((number, line)),
+                Section::None => return error(number, "expected a section, such as `@TABLE`"),
+                Section::Rule | Section::Other => {}
+            }
+        }
+
+        let mut rule = if !table.is_empty() {
+            parse_table(&table)?
+        } else if !tree.is_empty() {
+            parse_tree(&tree)?
+        } else {
+            return error(0, "missing `@TABLE` or `@TREE` section");
+        };
+
+        rule.name = name;
+        Ok(rule)
+    }
+}
+
+/// Parses a `@TREE` section.
+fn parse_tree(lines: &[(usize, &str)]) -> Result<RuleTree, ParseRuleTreeError> {
+    let mut states = None;
+    let mut neighbors = None;
+    let mut nodes = Vec::new();
+    let mut levels = Vec::new();
+
+    for &(number, line) in lines {
+        if let Some((key, value)) = split_once(line, '=') {
+            let value = value.trim().parse::<usize>();
+            match (key.trim(), value) {
+                ("num_states", Ok(n @ 1..=256)) => states = Some(n),
+                ("num_neighbors", Ok(4)) => neighbors = Some(Neighborhood::VonNeumann),
+                ("num_neighbors", Ok(8)) => neighbors = Some(Neighborhood::Moore),
+                ("num_nodes", Ok(_)) => {}
+                _ => return error(number, "invalid tree parameter"),
+            }
+            continue;
+        }
+
+        let states = match states {
+            Some(states) => states,
+            None => return error(number, "expected `num_states` before the nodes"),
+        };
+
+        let mut values = line.split_whitespace().map(str::parse::<usize>);
+        let level = match values.next() {
+            Some(Ok(level)) if level > 0 => level,
+            _ => return error(number, "invalid node level"),
+        };
+
+        let node = nodes.len() / states;
+        let start = nodes.len();
+        for value in values {
+            let value = match value {
+                Ok(value) if level == 1 && value < states => value as u32,
+                Ok(child) if level > 1 && child < node && levels[child] == level - 1 => {
+                    (child * states) as u32
+                }
+                _ => return error(number, "invalid node value"),
+            };
+            nodes.push(value);
+        }
+
+        if nodes.len() - start != states {
+            return error(number, "a node must have exactly `num_states` values");
+        }
+        levels.push(level);
+    }
+
+    let (states, neighborhood) = match (states, neighbors) {
+        (Some(states), Some(neighborhood)) => (states, neighborhood),
+        _ => return error(0, "missing `num_states` or `num_neighbors`"),
+    };
+
+    match levels.last() {
+        Some(&level) if level == neighborhood.size() + 1 => {}
+        _ => return error(0, "the last node must be the root of the tree"),
+    }
+
+    Ok(RuleTree {
+        name: None,
+        states,
+        neighborhood,
+        root: (nodes.len() - states) as u32,
+        nodes,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Var(usize),
+    Set(Set),
+}
+
+/// A transition with every bound variable replaced by a state.
+struct Expanded {
+    inputs: Vec<Set>,
+    output: u8,
+}
+
+/// Parses a `@TABLE` section, and compiles it into a tree.
+fn parse_table(lines: &[(usize, &str)]) -> Result<RuleTree, ParseRuleTreeError> {
+    let mut states = None;
+    let mut neighborhood = None;
+    let mut symmetries = None;
+    let mut vars: Vec<(&str, Set)> = Vec::new();
+    let mut transitions = Vec::new();
+
+    for &(number, line) in lines {
+        if let Some(rest) = line.strip_prefix("var ") {
+            let states = states.unwrap_or(256);
+            let (name, value) = match split_once(rest, '=') {
+                Some((name, value)) => (name.trim(), value.trim()),
+                None => return error(number, "expected `var name={...}`"),
+            };
+
+            match parse_token(value, states, &vars) {
+                Some(Token::Set(set)) if !name.is_empty() => vars.push((name, set)),
+                Some(Token::Var(i)) if !name.is_empty() => vars.push((name, vars[i].1)),
+                _ => return error(number, "invalid variable"),
+            }
+        } else if let Some((key, value)) = split_once(line, ':') {
+            let value = value.trim();
+            match key.trim() {
+                "n_states" => match value.parse::<usize>() {
+                    Ok(n @ 1..=256) => states = Some(n),
+                    _ => return error(number, "`n_states` must be between 1 and 256"),
+                },
+                "neighborhood" => match value {
+                    "vonNeumann" => neighborhood = Some(Neighborhood::VonNeumann),
+                    "Moore" => neighborhood = Some(Neighborhood::Moore),
+                    _ => return error(number, "unsupported neighborhood"),
+                },
+                "symmetries" => symmetries = Some((number, value)),
+                _ => return error(number, "unknown table parameter"),
+            }
+        } else {
+            let (states, neighborhood) = match (states, neighborhood) {
+                (Some(states), Some(neighborhood)) => (states, neighborhood),
+                _ => return error(number, "expected `n_states` and `neighborhood` first"),
+            };
+
+            let tokens: Option<Vec<Token>> = if line.contains(',') {
+                split_top_level(line)
+                    .map(|token| parse_token(token.trim(), states, &vars))
+                    .collect()
+            } else {
+                // every character is its own state
+                line.char_indices()
+                    .filter(|(_, c)| !c.is_whitespace())
+                    .map(|(i, c)| parse_token(&line[i..i + c.len_utf8()], states, &vars))
+                    .collect()
+            };
+
+            match tokens {
+                Some(tokens) if tokens.len() == neighborhood.size() + 2 => {
+                    transitions.push((number, tokens))
+                }
+                Some(_) => return error(number, "wrong number of states in transition"),
+                None => return error(number, "invalid state or unknown variable"),
+            }
+        }
+    }
+
+    let (states, neighborhood) = match (states, neighborhood) {
+        (Some(states), Some(neighborhood)) => (states, neighborhood),
+        _ => return error(0, "missing `n_states` or `neighborhood`"),
+    };
+
+    let (symmetry_line, symmetries) = symmetries.unwrap_or((0, "none"));
+    let permutations = match symmetry_permutations(neighborhood, symmetries) {
+        Some(permutations) => permutations,
+        None if symmetries == "permute" => Vec::new(),
+        None => return error(symmetry_line, "unsupported symmetries"),
+    };
+
+    let mut expanded = Vec::new();
+    for (number, tokens) in transitions {
+        let variants = if symmetries == "permute" {
+            distinct_permutations(&tokens)
+        } else {
+            let mut variants: Vec<Vec<Token>> = Vec::new();
+            for permutation in &permutations {
+                let mut variant = tokens.clone();
+                for (i, &from) in permutation.iter().enumerate() {
+                    variant[i + 1] = tokens[from + 1].clone();
+                }
+
+                if !variants.contains(&variant) {
+                    variants.push(variant);
+                }
+            }
+            variants
+        };
+
+        for variant in variants {
+            expand(&variant, &vars, &mut expanded).or_else(|reason| error(number, reason))?;
+        }
+    }
+
+    let mut builder = Builder {
+        transitions: &expanded,
+        order: neighborhood.tree_order(),
+        states,
+        nodes: Vec::new(),
+        nodes_dedup: BTreeMap::new(),
+        memo: BTreeMap::new(),
+    };
+
+    let candidates: Vec<u32> = (0..expanded.len() as u32).collect();
+    let root = builder.build(0, &candidates);
+
+    Ok(RuleTree {
+        name: None,
+        states,
+        neighborhood,
+        nodes: builder.nodes,
+        root,
+    })
+}
+
+/// Like `str::split_once`, which isn't available in older versions of rust.
+fn split_once(s: &str, delimiter: char) -> Option<(&str, &str)> {
+    s.find(delimiter).map(|i| (&s[..i], &s[i + 1..]))
+}
+
+/// Splits on commas, except ones inside braces.
+fn split_top_level(s: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    let mut start = 0;
+    let mut done = false;
+    let mut chars = s.char_indices();
+
+    core::iter::from_fn(move || loop {
+        if done {
+            return None;
+        }
+
+        match chars.next() {
+            Some((_, '{')) => depth += 1,
+            Some((_, '}')) => depth -= 1,
+            Some((i, ',')) if depth == 0 => {
+                let token = &s[start..i];
+                start = i + 1;
+                return Some(token);
+            }
+            Some(_) => {}
+            None => {
+                done = true;
+                return Some(&s[start..]);
+            }
+        }
+    })
+}
+
+/// Parses a state, a variable name, or a set like `{0,1,a}`.
+fn parse_token(token: &str, states: usize, vars: &[(&str, Set)]) -> Option<Token> {
+    if let Some(inner) = token.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+        let mut set = [0; 4];
+        for item in split_top_level(inner) {
+            match parse_token(item.trim(), states, vars)? {
+                Token::Set(other) => {
+                    for (word, other) in set.iter_mut().zip(other.iter()) {
+                        *word |= other;
+                    }
+                }
+                Token::Var(i) => {
+                    for (word, other) in set.iter_mut().zip(vars[i].1.iter()) {
+                        *word |= other;
+                    }
+                }
+            }
+        }
+        Some(Token::Set(set))
+    } else if let Ok(state) = token.parse::<usize>() {
+        if state < states {
+            Some(Token::Set(set_of(state)))
+        } else {
+            None
+        }
+    } else {
+        // later definitions shadow earlier ones
+        vars.iter()
+            .rposition(|&(name, _)| name == token)
+            .map(Token::Var)
+    }
+}
+
+/// Gets the permutations of the neighbors for a symmetry. Each permutation maps a neighbor to
+/// the neighbor it's taken from.
+fn symmetry_permutations(neighborhood: Neighborhood, symmetries: &str) -> Option<Vec<Vec<usize>>> {
+    let len = neighborhood.size();
+    let moore = neighborhood == Neighborhood::Moore;
+
+    // rotations are in steps of 45 degrees for moore neighborhoods
+    let (rotations, step, reflect) = match symmetries {
+        "none" => (1, 1, false),
+        "rotate4" if moore => (4, 2, false),
+        "rotate4" => (4, 1, false),
+        "rotate8" if moore => (8, 1, false),
+        "reflect_horizontal" => (1, 1, true),
+        "rotate4reflect" if moore => (4, 2, true),
+        "rotate4reflect" => (4, 1, true),
+        "rotate8reflect" if moore => (8, 1, true),
+        _ => return None,
+    };
+
+    let mut permutations = Vec::new();
+    for rotation in 0..rotations {
+        let rotated: Vec<usize> = (0..len).map(|i| (i + rotation * step) % len).collect();
+        if reflect {
+            permutations.push(rotated.iter().map(|&i| (len - i) % len).collect());
+        }
+        permutations.push(rotated);
+    }
+
+    Some(permutations)
+}
+
+/// Gets every distinct ordering of a transition's neighbors.
+fn distinct_permutations(tokens: &[Token]) -> Vec<Vec<Token>> {
+    let neighbors = &tokens[1..tokens.len() - 1];
+
+    // give equal tokens equal keys, then walk through the permutations of the keys in order
+    let mut keys: Vec<usize> = neighbors
+        .iter()
+        .map(|token| neighbors.iter().position(|other| other == token).unwrap())
+        .collect();
+    keys.sort_unstable();
+
+    let mut variants = Vec::new();
+    loop {
+        let mut variant = Vec::with_capacity(tokens.len());
+        variant.push(tokens[0].clone());
+        variant.extend(keys.iter().map(|&key| neighbors[key].clone()));
+        variant.push(tokens[tokens.len() - 1].clone());
+        variants.push(variant);
+
+        // next lexicographic permutation
+        let i = match (1..keys.len()).rev().find(|&i| keys[i - 1] < keys[i]) {
+            Some(i) => i,
+            None => return variants,
+        };
+        let j = (i..keys.len())
+            .rev()
+            .find(|&j| keys[i - 1] < keys[j])
+            .unwrap();
+        keys.swap(i - 1, j);
+        keys[i..].reverse();
+    }
+}
+
+/// Replaces every bound variable (one which appears more than once) with each of its states.
+fn expand(
+    tokens: &[Token],
+    vars: &[(&str, Set)],
+    out: &mut Vec<Expanded>,
+) -> Result<(), &'static str> {
+    let mut bound: Vec<usize> = Vec::new();
+    for token in tokens {
+        if let Token::Var(var) = *token {
+            let count = tokens.iter().filter(|&t| *t == Token::Var(var)).count();
+            if count > 1 && !bound.contains(&var) {
+                bound.push(var);
+            }
+        }
+    }
+
+    let (inputs, output) = tokens.split_at(tokens.len() - 1);
+    match output[0] {
+        Token::Var(var) if !bound.contains(&var) => {
+            return Err("an output variable must also be an input");
+        }
+        Token::Set(set) if set.iter().map(|word| word.count_ones()).sum::<u32>() != 1 => {
+            return Err("the output must be a single state");
+        }
+        _ => {}
+    }
+
+    let values: Vec<Vec<usize>> = bound
+        .iter()
+        .map(|&var| {
+            (0..256)
+                .filter(|&s| set_contains(&vars[var].1, s))
+                .collect()
+        })
+        .collect();
+
+    if values.iter().any(Vec::is_empty) {
+        return Ok(());
+    }
+
+    // walk through every combination, like an odometer
+    let mut choice = vec![0; bound.len()];
+    loop {
+        let resolve = |token: &Token| match *token {
+            Token::Var(var) => match bound.iter().position(|&b| b == var) {
+                Some(i) => set_of(values[i][choice[i]]),
+                None => vars[var].1,
+            },
+            Token::Set(set) => set,
+        };
+
+        let output = resolve(&output[0]);
+        out.push(Expanded {
+            inputs: inputs.iter().map(resolve).collect(),
+            output: (0..256).find(|&s| set_contains(&output, s)).unwrap() as u8,
+        });
+
+        let mut i = 0;
+        loop {
+            if i == choice.len() {
+                return Ok(());
+            }
+
+            choice[i] += 1;
+            if choice[i] < values[i].len() {
+                break;
+            }
+
+            choice[i] = 0;
+            i += 1;
+        }
+    }
+}
+
+/// Builds a tree out of a list of transitions, merging identical nodes.
+struct Builder<'a> {
+    transitions: &'a [Expanded],
+    order: &'static [usize],
+    states: usize,
+    nodes: Vec<u32>,
+    nodes_dedup: BTreeMap<(usize, Vec<u32>), u32>,
+    memo: BTreeMap<(usize, Vec<u32>), u32>,
+}
+
+impl Builder<'_> {
+    /// Builds the node at a depth, given the transitions which can still match. Returns its
+    /// offset.
+    fn build(&mut self, depth: usize, candidates: &[u32]) -> u32 {
+        if let Some(&offset) = self.memo.get(&(depth, candidates.to_vec())) {
+            return offset;
+        }
+
+        let column = self.order[depth];
+        let last = depth == self.order.len() - 1;
+        let mut children = Vec::with_capacity(self.states);
+
+        for state in 0..self.states {
+            let transitions = self.transitions;
+            let mut matching = candidates
+                .iter()
+                .copied()
+                .filter(|&t| set_contains(&transitions[t as usize].inputs[column], state));
+
+            let child = if last {
+                // the first matching transition wins, otherwise the cell doesn't change
+                match matching.next() {
+                    Some(t) => transitions[t as usize].output as u32,
+                    None => state as u32,
+                }
+            } else {
+                let matching: Vec<u32> = matching.collect();
+                self.build(depth + 1, &matching)
+            };
+
+            children.push(child);
+        }
+
+        let nodes = &mut self.nodes;
+        let offset = *self
+            .nodes_dedup
+            .entry((depth, children))
+            .or_insert_with_key(|(_, children)| {
+                let offset = nodes.len() as u32;
+                nodes.extend_from_slice(children);
+                offset
+            });
+
+        self.memo.insert((depth, candidates.to_vec()), offset);
+        offset
+    }
+}
@@ -0,0 +1,137 @@
+//! A small parser for command line arguments.
+
+use crate::Error;
+use cgol::Rule;
+use std::{fmt::Display, str::FromStr, vec};
+
+/// A command line argument.
+pub enum Arg {
+    /// A flag like `--size` or `-h`, without the dashes.
+    Flag(String),
+    /// Anything else, like a file name.
+    Positional(String),
+}
+
+/// Splits command line arguments into flags and positional arguments.
+pub struct Parser {
+    args: vec::IntoIter<String>,
+    /// The value after an `=` in the last flag, like `10` in `--gens=10`.
+    pending: Option<String>,
+}
+
+impl Parser {
+    pub fn new(args: impl IntoIterator<Item = String>) -> Parser {
+        Parser {
+            args: args.into_iter().collect::<Vec<_>>().into_iter(),
+            pending: None,
+        }
+    }
+
+    /// Gets the next argument.
+    ///
+    /// # Errors
+    /// Returns an error if the last flag was given a value with `=` which wasn't used.
+    pub fn next(&mut self) -> Result<Option<Arg>, Error> {
+        if let Some(value) = self.pending.take() {
+            return Err(Error::Usage(format!("unexpected value `{}`", value)));
+        }
+
+        let arg = match self.args.next() {
+            Some(arg) => arg,
+            None => return Ok(None),
+        };
+
+        let flag = match arg.strip_prefix("--").or_else(|| arg.strip_prefix('-')) {
+            Some(flag) if !flag.is_empty() => flag,
+            _ => return Ok(Some(Arg::Positional(arg))),
+        };
+
+        Ok(Some(Arg::Flag(match flag.split_once('=') {
+            Some((flag, value)) => {
+                self.pending = Some(value.to_string());
+                flag.to_string()
+            }
+            None => flag.to_string(),
+        })))
+    }
+
+    /// Gets the value of a flag, which is either after an `=` or the next argument.
+    pub fn value(&mut self, flag: &str) -> Result<String, Error> {
+        self.pending
+            .take()
+            .or_else(|| self.args.next())
+            .ok_or_else(|| Error::Usage(format!("`--{}` needs a value", flag)))
+    }
+
+    /// Gets and parses the value of a flag.
+    pub fn parse<T>(&mut self, flag: &str) -> Result<T, Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self.value(flag)?;
+        value.parse().map_err(|err| {
+            Error::Usage(format!(
+                "invalid value `{}` for `--{}`: {}",
+                value, flag, err
+            ))
+        })
+    }
+
+    /// Gets and parses a size like `200x100`.
+    pub fn size(&mut self, flag: &str) -> Result<(usize, usize), Error> {
+        let value = self.value(flag)?;
+        let size = value.split_once('x').and_then(|(width, height)| {
+            match (width.parse(), height.parse()) {
+                (Ok(width), Ok(height)) if width != 0 && height != 0 => Some((width, height)),
+                _ => None,
+            }
+        });
+
+        size.ok_or_else(|| {
+            Error::Usage(format!(
+                "invalid size `{}` for `--{}`, expected something like `200x100`",
+                value, flag
+            ))
+        })
+    }
+}
+
+/// Returns an error for a flag that isn't supported.
+pub fn unknown(flag: &str) -> Error {
+    Error::Usage(format!("unknown flag `--{}`", flag))
+}
+
+/// Returns an error for a positional argument that isn't expected.
+pub fn unexpected(arg: &str) -> Error {
+    Error::Usage(format!("unexpected argument `{}`", arg))
+}
+
+/// Returns an error for a positional argument that is missing.
+pub fn missing(name: &str) -> Error {
+    Error::Usage(format!("missing {}", name))
+}
+
+/// The options for setting up the board which most commands share.
+#[derive(Default)]
+pub struct Board {
+    /// The size of the board, instead of the pattern's size.
+    pub size: Option<(usize, usize)>,
+    /// How many dead cells to add around the pattern.
+    pub pad: usize,
+    /// The rule to run, instead of the pattern's rule.
+    pub rule: Option<Rule>,
+}
+
+impl Board {
+    /// Handles one of the board's flags, returning `false` if it isn't one.
+    pub fn flag(&mut self, flag: &str, args: &mut Parser) -> Result<bool, Error> {
+        match flag {
+            "size" => self.size = Some(args.size(flag)?),
+            "pad" => self.pad = args.parse(flag)?,
+            "rule" => self.rule = Some(args.parse(flag)?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
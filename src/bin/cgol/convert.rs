@@ -0,0 +1,35 @@
+//! The `convert` command, which converts a pattern between formats.
+
+use crate::{
+    args::{self, Arg, Parser},
+    files, Error,
+};
+use std::{path::PathBuf, process::ExitCode};
+
+pub const USAGE: &str = "\
+Usage: cgol convert INPUT OUTPUT
+
+Converts a pattern between `.rle` and `.cells`, picking the formats by the file extensions. Either
+file can be `-` for standard input or output, where the output is written as RLE.";
+
+pub fn main(mut args: Parser) -> Result<ExitCode, Error> {
+    let mut files = Vec::new();
+
+    while let Some(arg) = args.next()? {
+        match arg {
+            Arg::Flag(flag) => return Err(args::unknown(&flag)),
+            Arg::Positional(arg) if files.len() == 2 => return Err(args::unexpected(&arg)),
+            Arg::Positional(arg) => files.push(PathBuf::from(arg)),
+        }
+    }
+
+    match files.as_slice() {
+        [input, output] => {
+            let game = files::load(input)?;
+            files::save(&game, output)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        [_] => Err(args::missing("an output file")),
+        _ => Err(args::missing("an input and output file")),
+    }
+}
@@ -0,0 +1,118 @@
+//! Loading and saving patterns, picking the format by the file extension.
+
+use crate::{args::Board, Error};
+use cgol::{Cell, Game};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::Path,
+};
+
+/// The most cells a board can have, which is the same limit as for patterns.
+const MAX_AREA: usize = 1 << 28;
+
+/// Loads a pattern from a `.rle` or `.cells` file, or standard input if the path is `-`. Other
+/// files are read as whichever of the two they parse as.
+pub fn load(path: &Path) -> Result<Game, Error> {
+    let failed =
+        |err: &dyn std::fmt::Display| Error::Failed(format!("{}: {}", path.display(), err));
+
+    let text = if path == Path::new("-") {
+        let mut text = String::new();
+        io::stdin()
+            .read_to_string(&mut text)
+            .map_err(|err| failed(&err))?;
+        text
+    } else {
+        fs::read_to_string(path).map_err(|err| failed(&err))?
+    };
+
+    let game = match extension(path).as_deref() {
+        Some("rle") => Game::from_rle(&text),
+        Some("cells") => Game::from_plaintext(&text),
+        _ => Game::from_rle(&text).or_else(|_| Game::from_plaintext(&text)),
+    };
+
+    game.map_err(|err| failed(&err))
+}
+
+/// Saves a pattern as a `.rle` or `.cells` file, or RLE to standard output if the path is `-`.
+pub fn save(game: &Game, path: &Path) -> Result<(), Error> {
+    let failed = |err: io::Error| Error::Failed(format!("{}: {}", path.display(), err));
+
+    if path == Path::new("-") {
+        return io::stdout()
+            .write_all(game.to_rle().as_bytes())
+            .map_err(failed);
+    }
+
+    let text = match extension(path).as_deref() {
+        Some("rle") => game.to_rle(),
+        Some("cells") => game.to_plaintext(),
+        _ => {
+            return Err(Error::Usage(format!(
+                "{}: unknown format, expected a `.rle` or `.cells` file",
+                path.display()
+            )))
+        }
+    };
+
+    fs::write(path, text).map_err(failed)
+}
+
+/// Gets the lowercase extension of a file.
+pub fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase)
+}
+
+/// Copies a pattern into the middle of a game of a specific size, which runs the same rule. If the
+/// pattern is bigger, it is cut off.
+pub fn place(pattern: &Game, width: usize, height: usize) -> Game {
+    let mut game = Game::with_rule(width, height, pattern.rule());
+
+    // where the pattern's corner goes, which is negative if it's bigger
+    let left = (width as isize - pattern.width() as isize) / 2;
+    let top = (height as isize - pattern.height() as isize) / 2;
+
+    for (row, col) in pattern.alive_cells() {
+        let (y, x) = (row as isize + top, col as isize + left);
+        if (0..height as isize).contains(&y) && (0..width as isize).contains(&x) {
+            game[(x as usize, y as usize)] = Cell::Alive;
+        }
+    }
+
+    game
+}
+
+impl Board {
+    /// Loads a pattern, and sets up the board around it.
+    pub fn load(&self, path: &Path) -> Result<Game, Error> {
+        let pattern = load(path)?;
+
+        let padded = |n: usize| self.pad.checked_mul(2)?.checked_add(n);
+        let size = match self.size {
+            Some(size) => Some(size),
+            None => padded(pattern.width()).zip(padded(pattern.height())),
+        };
+        let (width, height) = size
+            .filter(|&(width, height)| width.saturating_mul(height) <= MAX_AREA)
+            .ok_or_else(|| {
+                Error::Usage(format!(
+                    "the board can have at most {} cells, try a smaller `--size` or `--pad`",
+                    MAX_AREA
+                ))
+            })?;
+        let mut game = if (width, height) == (pattern.width(), pattern.height()) {
+            pattern
+        } else {
+            place(&pattern, width, height)
+        };
+
+        if let Some(rule) = self.rule {
+            game.set_rule(rule);
+        }
+        Ok(game)
+    }
+}
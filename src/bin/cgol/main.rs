@@ -0,0 +1,109 @@
+//! The `cgol` command line tool, which is built with the `cli` feature.
+//!
+//! Exits with `0` on success, `1` if reading, writing or parsing a file fails, `2` if the
+//! arguments are invalid, and `3` if `stats --until-stable` runs out of generations.
+
+/// The usage of the options in [`args::Board`], for the end of each command's usage.
+macro_rules! board_usage {
+    () => {
+        "
+  --size WxH        The size of the board, which wraps around at the edges, with the pattern in
+                    the middle [default: the pattern's size plus padding]
+  --pad N           Dead cells to add around each side of the pattern [default: 0]
+  --rule RULE       The rule to run, like `B3/S23` [default: the pattern's rule]"
+    };
+}
+
+mod args;
+mod convert;
+mod files;
+mod render;
+mod run;
+mod stats;
+mod view;
+
+use args::Parser;
+use std::{env, process::ExitCode};
+
+const USAGE: &str = "\
+Usage: cgol [COMMAND] [ARGS]
+
+Commands:
+  view     Open a full screen viewer (the default)
+  run      Run a pattern for some generations and save the result
+  convert  Convert a pattern between `.rle` and `.cells`
+  stats    Print statistics about a pattern as JSON
+  render   Render a pattern as PNG, SVG or an animated GIF
+
+Run `cgol COMMAND --help` for more about a command.
+
+Exit codes:
+  0  success
+  1  reading, writing or parsing a file failed
+  2  invalid arguments
+  3  `stats --until-stable` didn't stabilize within `--max-gens`";
+
+/// An error which ends the program.
+pub enum Error {
+    /// The arguments are invalid.
+    Usage(String),
+    /// Reading, writing or parsing a file failed.
+    Failed(String),
+}
+
+impl Error {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            Error::Usage(_) => ExitCode::from(2),
+            Error::Failed(_) => ExitCode::FAILURE,
+        }
+    }
+}
+
+type Command = fn(Parser) -> Result<ExitCode, Error>;
+
+fn main() -> ExitCode {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let commands: [(&str, Command, &str); 5] = [
+        ("view", view::main, view::USAGE),
+        ("run", run::main, run::USAGE),
+        ("convert", convert::main, convert::USAGE),
+        ("stats", stats::main, stats::USAGE),
+        ("render", render::main, render::USAGE),
+    ];
+
+    let (name, command, usage) = match args.first().map(String::as_str) {
+        None | Some("help") | Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Some(name) => match commands.iter().find(|(command, ..)| *command == name) {
+            Some(&(name, command, usage)) => {
+                args.remove(0);
+                (name, command, usage)
+            }
+            // `cgol FILE` opens the viewer
+            None => ("view", view::main as Command, view::USAGE),
+        },
+    };
+
+    if args.iter().any(|arg| arg == "-h" || arg == "--help") {
+        println!("{}", usage);
+        return ExitCode::SUCCESS;
+    }
+
+    match command(Parser::new(args)) {
+        Ok(code) => code,
+        Err(err) => {
+            match &err {
+                Error::Usage(message) => eprintln!(
+                    "error: {}\n\nRun `cgol {} --help` for usage.",
+                    message, name
+                ),
+                Error::Failed(message) => eprintln!("error: {}", message),
+            }
+            err.exit_code()
+        }
+    }
+}
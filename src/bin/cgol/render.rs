@@ -0,0 +1,170 @@
+//! The `render` command, which renders a pattern as an image.
+
+use crate::{
+    args::{self, Arg, Board, Parser},
+    Error,
+};
+use cgol::{
+    image::{gif::GifOptions, svg::SvgOptions, ImageOptions},
+    Rect,
+};
+use std::{
+    fs::{self, File},
+    io::BufWriter,
+    path::PathBuf,
+    process::ExitCode,
+};
+
+pub const USAGE: &str = concat!(
+    "\
+Usage: cgol render FILE [--png OUT] [--svg OUT] [--gif OUT] [OPTIONS]
+
+Renders a pattern from a `.rle` or `.cells` file (or `-` for standard input) as images. At least
+one output is needed.
+
+Options:
+  --png FILE        Render the last generation as a PNG
+  --svg FILE        Render the last generation as an SVG
+  --gif FILE        Record every generation as an animated GIF
+  --gens N          How many generations to run [default: 0]
+  --cell-size N     The size of each cell in pixels [default: 4]
+  --grid            Draw lines between cells
+  --crop X,Y,W,H    Only render the cells inside a rectangle
+  --zoom-out N      Shade each PNG or GIF square by how many of the 2^N by 2^N cells it covers are
+                    alive [default: 0]
+  --delay N         How long each GIF frame is shown, in hundredths of a second [default: 10]",
+    board_usage!()
+);
+
+/// The most pixels an image can have, so rendering can't run out of memory.
+const MAX_PIXELS: usize = 1 << 28;
+
+pub fn main(mut args: Parser) -> Result<ExitCode, Error> {
+    let mut file = None;
+    let (mut png, mut svg, mut gif) = (None, None, None);
+    let mut gens = 0;
+    let mut cell_size = 4;
+    let mut grid = false;
+    let mut crop = None;
+    let mut zoom_out = 0;
+    let mut delay = 10;
+    let mut board = Board::default();
+
+    while let Some(arg) = args.next()? {
+        match arg {
+            Arg::Flag(flag) => match flag.as_str() {
+                "png" => png = Some(PathBuf::from(args.value(&flag)?)),
+                "svg" => svg = Some(PathBuf::from(args.value(&flag)?)),
+                "gif" => gif = Some(PathBuf::from(args.value(&flag)?)),
+                "gens" => gens = args.parse(&flag)?,
+                "cell-size" => cell_size = args.parse(&flag)?,
+                "grid" => grid = true,
+                "crop" => crop = Some(rect(&args.value(&flag)?)?),
+                "zoom-out" => zoom_out = args.parse(&flag)?,
+                "delay" => delay = args.parse(&flag)?,
+                _ if board.flag(&flag, &mut args)? => {}
+                _ => return Err(args::unknown(&flag)),
+            },
+            Arg::Positional(arg) if file.is_none() => file = Some(PathBuf::from(arg)),
+            Arg::Positional(arg) => return Err(args::unexpected(&arg)),
+        }
+    }
+
+    let file = file.ok_or_else(|| args::missing("a pattern file"))?;
+    if png.is_none() && svg.is_none() && gif.is_none() {
+        return Err(args::missing("an output, like `--png out.png`"));
+    }
+
+    if cell_size == 0 {
+        return Err(Error::Usage("`--cell-size` must be at least 1".into()));
+    }
+
+    let mut game = board.load(&file)?;
+    if let Some(crop) = crop {
+        if crop.clamp(game.width(), game.height()).is_empty() {
+            return Err(Error::Usage(format!(
+                "`--crop` is outside of the {}x{} board",
+                game.width(),
+                game.height()
+            )));
+        }
+    }
+
+    let options = ImageOptions {
+        cell_size,
+        grid: if grid { Some([192; 3]) } else { None },
+        crop,
+        zoom_out,
+        ..ImageOptions::default()
+    };
+
+    // check the size before anything is rendered, since a huge image can't even be allocated
+    let (width, height) = options
+        .image_size(&game)
+        .filter(|&(width, height)| width.saturating_mul(height) <= MAX_PIXELS)
+        .ok_or_else(|| Error::Usage("the image is too big, try a smaller `--cell-size`".into()))?;
+    if gif.is_some() && (width > 65535 || height > 65535) {
+        return Err(Error::Usage(format!(
+            "the gif would be {}x{} pixels, but gifs can be at most 65535x65535",
+            width, height
+        )));
+    }
+
+    let failed =
+        |path: &PathBuf, err: std::io::Error| Error::Failed(format!("{}: {}", path.display(), err));
+
+    // the gif records every generation, which leaves the game at the last one for the others
+    match &gif {
+        Some(path) => {
+            let writer = BufWriter::new(File::create(path).map_err(|err| failed(path, err))?);
+            let options = GifOptions {
+                image: options.clone(),
+                delay,
+                ..GifOptions::default()
+            };
+            game.record_gif(writer, gens, &options)
+                .map_err(|err| failed(path, err))?;
+        }
+        None => {
+            for _ in 0..gens {
+                game.tick();
+            }
+        }
+    }
+
+    if let Some(path) = &png {
+        let writer = BufWriter::new(File::create(path).map_err(|err| failed(path, err))?);
+        game.render(&options)
+            .write_png(writer)
+            .map_err(|err| failed(path, err))?;
+    }
+
+    if let Some(path) = &svg {
+        let options = SvgOptions {
+            cell_size,
+            grid: options.grid,
+            crop,
+            ..SvgOptions::default()
+        };
+        fs::write(path, game.to_svg(&options)).map_err(|err| failed(path, err))?;
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Parses a rectangle like `10,20,100,50`.
+fn rect(value: &str) -> Result<Rect, Error> {
+    let numbers: Vec<usize> = value
+        .split(',')
+        .map(|n| n.trim().parse())
+        .collect::<Result<_, _>>()
+        .unwrap_or_default();
+
+    match *numbers.as_slice() {
+        [x, y, width, height] => Ok(Rect::new(x, y, width, height)),
+        _ => Err(Error::Usage(format!(
+            "invalid rectangle `{}` for `--crop`, expected something like `10,20,100,50`",
+            value
+        ))),
+    }
+}
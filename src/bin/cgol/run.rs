@@ -0,0 +1,52 @@
+//! The `run` command, which runs a pattern and saves the result.
+
+use crate::{
+    args::{self, Arg, Board, Parser},
+    files, Error,
+};
+use std::{path::PathBuf, process::ExitCode};
+
+pub const USAGE: &str = concat!(
+    "\
+Usage: cgol run FILE --gens N [OPTIONS]
+
+Runs a pattern from a `.rle` or `.cells` file (or `-` for standard input) for some generations,
+and saves the result.
+
+Options:
+  --gens N          How many generations to run
+  --out FILE        Where to save the result, as `.rle` or `.cells`, or `-` to write RLE to
+                    standard output [default: -]",
+    board_usage!()
+);
+
+pub fn main(mut args: Parser) -> Result<ExitCode, Error> {
+    let mut file = None;
+    let mut gens: Option<u64> = None;
+    let mut out = PathBuf::from("-");
+    let mut board = Board::default();
+
+    while let Some(arg) = args.next()? {
+        match arg {
+            Arg::Flag(flag) => match flag.as_str() {
+                "gens" => gens = Some(args.parse(&flag)?),
+                "out" => out = PathBuf::from(args.value(&flag)?),
+                _ if board.flag(&flag, &mut args)? => {}
+                _ => return Err(args::unknown(&flag)),
+            },
+            Arg::Positional(arg) if file.is_none() => file = Some(PathBuf::from(arg)),
+            Arg::Positional(arg) => return Err(args::unexpected(&arg)),
+        }
+    }
+
+    let file = file.ok_or_else(|| args::missing("a pattern file"))?;
+    let gens = gens.ok_or_else(|| args::missing("`--gens`"))?;
+
+    let mut game = board.load(&file)?;
+    for _ in 0..gens {
+        game.tick();
+    }
+
+    files::save(&game, &out)?;
+    Ok(ExitCode::SUCCESS)
+}
@@ -0,0 +1,199 @@
+//! The `stats` command, which prints statistics about a pattern as JSON.
+
+use crate::{
+    args::{self, Arg, Board, Parser},
+    Error,
+};
+use cgol::{Cell, Game};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    fmt::Write,
+    hash::Hasher,
+    path::PathBuf,
+    process::ExitCode,
+};
+
+pub const USAGE: &str = concat!(
+    "\
+Usage: cgol stats FILE [OPTIONS]
+
+Runs a pattern from a `.rle` or `.cells` file (or `-` for standard input), and prints statistics
+about it as JSON.
+
+Options:
+  --gens N          How many generations to run [default: 0]
+  --until-stable    Run until the pattern repeats, as a still life or an oscillator, and exit with
+                    3 if it doesn't within `--max-gens`
+  --max-gens N      The most generations to run with `--until-stable` [default: 100000]",
+    board_usage!()
+);
+
+pub fn main(mut args: Parser) -> Result<ExitCode, Error> {
+    let mut file = None;
+    let mut gens = 0;
+    let mut until_stable = false;
+    let mut max_gens = 100_000;
+    let mut board = Board::default();
+
+    while let Some(arg) = args.next()? {
+        match arg {
+            Arg::Flag(flag) => match flag.as_str() {
+                "gens" => gens = args.parse(&flag)?,
+                "until-stable" => until_stable = true,
+                "max-gens" => max_gens = args.parse(&flag)?,
+                _ if board.flag(&flag, &mut args)? => {}
+                _ => return Err(args::unknown(&flag)),
+            },
+            Arg::Positional(arg) if file.is_none() => file = Some(PathBuf::from(arg)),
+            Arg::Positional(arg) => return Err(args::unexpected(&arg)),
+        }
+    }
+
+    let file = file.ok_or_else(|| args::missing("a pattern file"))?;
+    let mut game = board.load(&file)?;
+    let initial = game.population();
+    // kept to check that states with the same hash really are the same
+    let first = if until_stable {
+        Some(game.clone())
+    } else {
+        None
+    };
+
+    let mut min = initial;
+    let mut max = initial;
+    // the last generation each hash was seen in
+    let mut seen = HashMap::new();
+    let mut cycle = None;
+
+    let limit = if until_stable { max_gens } else { gens };
+    loop {
+        if let Some(first) = &first {
+            if let Some(start) = seen.insert(hash(&game), game.generation()) {
+                if replay(first, start) == game {
+                    cycle = Some((start, game.generation() - start));
+                    break;
+                }
+            }
+        }
+
+        if game.generation() >= limit {
+            break;
+        }
+
+        game.tick();
+        let population = game.population();
+        min = min.min(population);
+        max = max.max(population);
+    }
+
+    let mut json = String::new();
+    let _ = write!(
+        json,
+        "{{\"file\":{},\"width\":{},\"height\":{},\"rule\":\"{}\",\"generations\":{},\
+         \"initial_population\":{},\"population\":{},\"min_population\":{},\
+         \"max_population\":{},\"bounding_box\":{}",
+        string(&file.display().to_string()),
+        game.width(),
+        game.height(),
+        game.rule(),
+        game.generation(),
+        initial,
+        game.population(),
+        min,
+        max,
+        bounding_box(&game),
+    );
+
+    if until_stable {
+        match cycle {
+            Some((start, period)) => {
+                let _ = write!(
+                    json,
+                    ",\"stable\":true,\"stable_since\":{},\"period\":{}",
+                    start, period
+                );
+            }
+            None => json.push_str(",\"stable\":false,\"stable_since\":null,\"period\":null"),
+        }
+    }
+    json.push('}');
+    println!("{}", json);
+
+    if until_stable && cycle.is_none() {
+        Ok(ExitCode::from(3))
+    } else {
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// Hashes which cells are alive, so states which look the same have the same hash.
+fn hash(game: &Game) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for row in game.rows() {
+        let mut word = 0u64;
+        for (col, cell) in row.enumerate() {
+            word = word << 1 | (cell == Cell::Alive) as u64;
+            if col % 64 == 63 {
+                hasher.write_u64(word);
+                word = 0;
+            }
+        }
+        hasher.write_u64(word);
+    }
+    hasher.finish()
+}
+
+/// Runs a copy of a game until it reaches a generation.
+fn replay(game: &Game, generation: u64) -> Game {
+    let mut game = game.clone();
+    while game.generation() < generation {
+        game.tick();
+    }
+    game
+}
+
+/// Finds the smallest rectangle which contains every alive cell, as a JSON object.
+fn bounding_box(game: &Game) -> String {
+    let alive = |cell: Cell| cell == Cell::Alive;
+
+    let mut rows = (0..game.height()).filter(|&row| game.row(row).any(alive));
+    let top = match rows.next() {
+        Some(top) => top,
+        None => return "null".to_string(),
+    };
+    let bottom = rows.next_back().unwrap_or(top);
+
+    let (mut left, mut right) = (game.width(), 0);
+    for row in top..=bottom {
+        if let Some(col) = game.row(row).position(alive) {
+            left = left.min(col);
+            right = right.max(game.width() - 1 - game.row(row).rev().position(alive).unwrap_or(0));
+        }
+    }
+
+    format!(
+        "{{\"x\":{},\"y\":{},\"width\":{},\"height\":{}}}",
+        left,
+        top,
+        right - left + 1,
+        bottom - top + 1
+    )
+}
+
+/// Writes a string as JSON.
+fn string(s: &str) -> String {
+    let mut json = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
@@ -0,0 +1,524 @@
+//! The full screen viewer.
+
+use crate::{
+    args::{self, Arg, Parser},
+    files, Error,
+};
+use cgol::{
+    game::{TerminalMode, TerminalOptions},
+    Cell, Game, Rect, Rule,
+};
+use crossterm::{
+    cursor,
+    event::{
+        self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+    },
+    execute, queue, style,
+    terminal::{self, ClearType},
+};
+use std::{
+    io::{self, Write},
+    panic,
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+pub const USAGE: &str = "\
+Usage: cgol [view] [FILE] [OPTIONS]
+
+Opens a full screen viewer for a pattern in a `.rle` or `.cells` file, or a random soup.
+
+Options:
+  --size WxH        The size of the board, which wraps around at the edges
+                    [default: the pattern or the screen, whichever is bigger]
+  --rule RULE       The rule to run, like `B3/S23` [default: the pattern's rule]
+  --random DENSITY  Fill the board randomly, with a chance from 0 to 1 of each cell being alive
+                    [default without a file: 0.3]
+  --speed GENS      How many generations to run each second [default: 10]
+  --save FILE       Where `s` saves the board, as `.rle` or `.cells` [default: saved.rle]
+
+Keys:
+  space  play or pause        n  step once            + -  faster or slower
+  arrows move the cursor      h j k l  pan             z x  zoom in or out
+  enter  toggle a cell        click  pick a cell      c  clear
+  r      random soup          s  save                 q  quit
+
+A click can only pick out one cell at full zoom. When zoomed out, it moves the cursor to the top
+left cell of the character instead, and enter toggles it.";
+
+/// How many generations can be run each second, from slowest to fastest.
+const SPEEDS: [u32; 14] = [
+    1, 2, 5, 10, 20, 30, 60, 120, 250, 500, 1000, 2000, 5000, 10000,
+];
+
+/// How long to wait between frames while playing.
+const FRAME: Duration = Duration::from_millis(16);
+
+/// How many cells each character covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Zoom {
+    /// Each cell is 2 characters wide, so it looks square.
+    Full,
+    /// Each character is 1 cell wide and 2 cells tall.
+    HalfBlock,
+    /// Each character is 2 cells wide and 4 cells tall.
+    Braille,
+    /// Like braille, but each dot covers `2^n` by `2^n` cells, and is shown if any are alive.
+    Out(u32),
+}
+
+/// How far the view can zoom out past braille.
+const MAX_ZOOM_OUT: u32 = 8;
+
+impl Zoom {
+    /// The width and height of the cells each character covers, where a width of `0` means each
+    /// cell is 2 characters wide.
+    fn cells(self) -> (usize, usize) {
+        match self {
+            Zoom::Full => (0, 1),
+            Zoom::HalfBlock => (1, 2),
+            Zoom::Braille => (2, 4),
+            Zoom::Out(n) => (2 << n, 4 << n),
+        }
+    }
+
+    fn zoom_in(self) -> Zoom {
+        match self {
+            Zoom::Out(n) if n > 1 => Zoom::Out(n - 1),
+            Zoom::Out(_) => Zoom::Braille,
+            Zoom::Braille => Zoom::HalfBlock,
+            _ => Zoom::Full,
+        }
+    }
+
+    fn zoom_out(self) -> Zoom {
+        match self {
+            Zoom::Full => Zoom::HalfBlock,
+            Zoom::HalfBlock => Zoom::Braille,
+            Zoom::Braille => Zoom::Out(1),
+            Zoom::Out(n) => Zoom::Out((n + 1).min(MAX_ZOOM_OUT)),
+        }
+    }
+}
+
+/// Runs the viewer with the arguments after `cgol` or `cgol view`.
+pub fn main(mut args: Parser) -> Result<ExitCode, Error> {
+    let mut file = None;
+    let mut size = None;
+    let mut rule: Option<Rule> = None;
+    let mut random: Option<f64> = None;
+    let mut speed = 10;
+    let mut save = PathBuf::from("saved.rle");
+
+    while let Some(arg) = args.next()? {
+        match arg {
+            Arg::Flag(flag) => match flag.as_str() {
+                "size" => size = Some(args.size(&flag)?),
+                "rule" => rule = Some(args.parse(&flag)?),
+                "random" => random = Some(args.parse(&flag)?),
+                "speed" => speed = args.parse(&flag)?,
+                "save" => save = PathBuf::from(args.value(&flag)?),
+                _ => return Err(args::unknown(&flag)),
+            },
+            Arg::Positional(arg) if file.is_none() => file = Some(PathBuf::from(arg)),
+            Arg::Positional(arg) => return Err(args::unexpected(&arg)),
+        }
+    }
+
+    if let Some(density) = random {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(Error::Usage(
+                "`--random` must be between 0 and 1".to_string(),
+            ));
+        }
+    }
+
+    let pattern = file.as_deref().map(files::load).transpose()?;
+
+    // by default, fill the screen at the closest zoom
+    let failed = |err: io::Error| Error::Failed(err.to_string());
+    let (cols, rows) = terminal::size().map_err(failed)?;
+    let screen = (cols as usize / 2, (rows as usize).saturating_sub(1));
+    let (width, height) = size.unwrap_or_else(|| match &pattern {
+        Some(pattern) => (
+            pattern.width().max(screen.0).max(1),
+            pattern.height().max(screen.1).max(1),
+        ),
+        None => (screen.0.max(1), screen.1.max(1)),
+    });
+
+    let mut game = match &pattern {
+        Some(pattern) => files::place(pattern, width, height),
+        None => Game::new(width, height),
+    };
+    if let Some(rule) = rule {
+        game.set_rule(rule);
+    }
+
+    let density = random.unwrap_or(0.3);
+    if random.is_some() || pattern.is_none() {
+        game.fill_random(density);
+    }
+
+    let speed = SPEEDS
+        .iter()
+        .position(|&s| s >= speed)
+        .unwrap_or(SPEEDS.len() - 1);
+
+    let mut viewer = Viewer {
+        x: game.width().saturating_sub(screen.0) / 2,
+        y: game.height().saturating_sub(screen.1) / 2,
+        cursor: (game.width() / 2, game.height() / 2),
+        game,
+        zoom: Zoom::Full,
+        playing: false,
+        speed,
+        density,
+        save,
+        message: None,
+        screen: (cols as usize, rows as usize),
+    };
+
+    let _guard = Guard::enter().map_err(failed)?;
+    viewer.run().map_err(failed)?;
+    Ok(ExitCode::SUCCESS)
+}
+
+/// Puts the terminal into full screen mode, and puts it back when it's dropped. When panicking,
+/// it's put back before the message is printed, so the message isn't lost with the alternate
+/// screen.
+struct Guard;
+
+impl Guard {
+    fn enter() -> io::Result<Guard> {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            Guard::restore();
+            hook(info);
+        }));
+
+        terminal::enable_raw_mode()?;
+        let guard = Guard;
+        execute!(
+            io::stdout(),
+            terminal::EnterAlternateScreen,
+            event::EnableMouseCapture,
+            cursor::Hide,
+        )?;
+        Ok(guard)
+    }
+
+    /// Puts the terminal back, which is harmless to do more than once.
+    fn restore() {
+        let _ = execute!(
+            io::stdout(),
+            event::DisableMouseCapture,
+            terminal::LeaveAlternateScreen,
+            cursor::Show,
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {
+        Guard::restore();
+    }
+}
+
+struct Viewer {
+    game: Game,
+    /// The cell in the top left corner of the screen.
+    x: usize,
+    y: usize,
+    /// The cell which is toggled with enter, as `(col, row)`.
+    cursor: (usize, usize),
+    zoom: Zoom,
+    playing: bool,
+    /// An index into [`SPEEDS`].
+    speed: usize,
+    /// The density of random soups.
+    density: f64,
+    save: PathBuf,
+    /// A message shown in the status line until the next key is pressed.
+    message: Option<String>,
+    /// The size of the terminal, in characters.
+    screen: (usize, usize),
+}
+
+impl Viewer {
+    fn run(&mut self) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        let mut last_tick = Instant::now();
+        // generations which should have run by now, but haven't yet
+        let mut owed = 0.0;
+        let mut dirty = true;
+
+        loop {
+            if dirty {
+                self.draw(&mut stdout)?;
+                dirty = false;
+            }
+
+            let timeout = if self.playing {
+                FRAME
+            } else {
+                Duration::from_secs(1)
+            };
+
+            if event::poll(timeout)? {
+                let event = event::read()?;
+                if !self.handle(event) {
+                    return Ok(());
+                }
+                dirty = true;
+            }
+
+            let now = Instant::now();
+            if self.playing {
+                owed += now.duration_since(last_tick).as_secs_f64() * SPEEDS[self.speed] as f64;
+                // don't try to catch up after a slow frame
+                let ticks = owed.floor().min(SPEEDS[self.speed] as f64 / 10.0 + 1.0);
+                for _ in 0..ticks as u32 {
+                    self.game.tick();
+                }
+                owed = (owed - ticks).min(1.0);
+                dirty |= ticks > 0.0;
+            } else {
+                owed = 0.0;
+            }
+            last_tick = now;
+        }
+    }
+
+    /// The size of the screen in cells, not counting the status line.
+    fn view_size(&self) -> (usize, usize) {
+        let (cols, rows) = (self.screen.0, self.screen.1.saturating_sub(1));
+        match self.zoom.cells() {
+            (0, h) => (cols / 2, rows * h),
+            (w, h) => (cols * w, rows * h),
+        }
+    }
+
+    /// Keeps the view inside the board.
+    fn clamp_view(&mut self) {
+        let (width, height) = self.view_size();
+        self.x = self.x.min(self.game.width().saturating_sub(width));
+        self.y = self.y.min(self.game.height().saturating_sub(height));
+    }
+
+    /// Moves the view so the cursor is on the screen.
+    fn follow_cursor(&mut self) {
+        let (width, height) = self.view_size();
+        let (col, row) = self.cursor;
+
+        if col < self.x {
+            self.x = col;
+        } else if col >= self.x + width {
+            self.x = col + 1 - width;
+        }
+
+        if row < self.y {
+            self.y = row;
+        } else if row >= self.y + height {
+            self.y = row + 1 - height;
+        }
+
+        self.clamp_view();
+    }
+
+    fn toggle(&mut self, (col, row): (usize, usize)) {
+        let cell = &mut self.game[(col, row)];
+        *cell = match *cell {
+            Cell::Alive => Cell::Dead,
+            Cell::Dead => Cell::Alive,
+        };
+    }
+
+    /// Handles an event, returning `false` to quit.
+    fn handle(&mut self, event: Event) -> bool {
+        match event {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                self.message = None;
+                return self.key(key);
+            }
+            Event::Mouse(mouse) => {
+                if let MouseEventKind::Down(MouseButton::Left) = mouse.kind {
+                    let (col, row) = (mouse.column as usize, mouse.row as usize);
+                    let (col, row) = match self.zoom.cells() {
+                        (0, _) => (col / 2, row),
+                        (w, h) => (col * w, row * h),
+                    };
+
+                    // terminals only report which character was clicked, so when zoomed out, the
+                    // click can't pick a cell, and only moves the cursor
+                    let (col, row) = (self.x + col, self.y + row);
+                    if col < self.game.width() && row < self.game.height() {
+                        self.cursor = (col, row);
+                        if self.zoom == Zoom::Full {
+                            self.toggle(self.cursor);
+                        }
+                    }
+                }
+            }
+            Event::Resize(cols, rows) => {
+                self.screen = (cols as usize, rows as usize);
+                self.clamp_view();
+            }
+            _ => {}
+        }
+
+        true
+    }
+
+    /// Handles a key press, returning `false` to quit.
+    fn key(&mut self, key: KeyEvent) -> bool {
+        let (width, height) = (self.game.width(), self.game.height());
+        let (view_width, view_height) = self.view_size();
+        let (col, row) = self.cursor;
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return false,
+
+            KeyCode::Char(' ') => self.playing = !self.playing,
+            KeyCode::Char('n') | KeyCode::Char('.') => {
+                self.playing = false;
+                self.game.tick();
+            }
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.speed = (self.speed + 1).min(SPEEDS.len() - 1);
+            }
+            KeyCode::Char('-') => self.speed = self.speed.saturating_sub(1),
+
+            KeyCode::Left => self.cursor.0 = col.saturating_sub(1),
+            KeyCode::Right => self.cursor.0 = (col + 1).min(width - 1),
+            KeyCode::Up => self.cursor.1 = row.saturating_sub(1),
+            KeyCode::Down => self.cursor.1 = (row + 1).min(height - 1),
+            KeyCode::Enter | KeyCode::Char('t') => self.toggle(self.cursor),
+
+            KeyCode::Char('h') => self.x = self.x.saturating_sub(view_width / 4 + 1),
+            KeyCode::Char('l') => self.x += view_width / 4 + 1,
+            KeyCode::Char('k') => self.y = self.y.saturating_sub(view_height / 4 + 1),
+            KeyCode::Char('j') => self.y += view_height / 4 + 1,
+            KeyCode::Char('z') => self.zoom = self.zoom.zoom_in(),
+            KeyCode::Char('x') => self.zoom = self.zoom.zoom_out(),
+
+            KeyCode::Char('c') => self.game.clear(),
+            KeyCode::Char('r') => self.game.fill_random(self.density),
+            KeyCode::Char('s') => {
+                self.message = Some(match files::save(&self.game, &self.save) {
+                    Ok(()) => format!("saved to {}", self.save.display()),
+                    Err(Error::Usage(err) | Error::Failed(err)) => err,
+                });
+            }
+            _ => {}
+        }
+
+        match key.code {
+            // panning moves the cursor onto the screen, instead of the screen to the cursor
+            KeyCode::Char('h' | 'j' | 'k' | 'l' | 'z' | 'x') => {
+                self.clamp_view();
+                let (view_width, view_height) = self.view_size();
+                let (col, row) = self.cursor;
+                self.cursor = (
+                    col.clamp(self.x, (self.x + view_width).max(1) - 1),
+                    row.clamp(self.y, (self.y + view_height).max(1) - 1),
+                );
+                self.cursor = (self.cursor.0.min(width - 1), self.cursor.1.min(height - 1));
+            }
+            _ => self.follow_cursor(),
+        }
+
+        true
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let (view_width, view_height) = self.view_size();
+        let view = Rect::new(self.x, self.y, view_width, view_height);
+
+        let lines: Vec<String> = match self.zoom {
+            Zoom::Full => {
+                let view = view.clamp(self.game.width(), self.game.height());
+                (view.y..view.y + view.height)
+                    .map(|row| {
+                        self.game
+                            .row(row)
+                            .skip(view.x)
+                            .take(view.width)
+                            .map(|cell| match cell {
+                                Cell::Alive => "██",
+                                Cell::Dead => "  ",
+                            })
+                            .collect()
+                    })
+                    .collect()
+            }
+            _ => {
+                let options = TerminalOptions {
+                    mode: match self.zoom {
+                        Zoom::HalfBlock => TerminalMode::HalfBlock,
+                        _ => TerminalMode::Braille,
+                    },
+                    color: None,
+                    viewport: Some(view),
+                    zoom_out: match self.zoom {
+                        Zoom::Out(n) => n,
+                        _ => 0,
+                    },
+                };
+                let text = self.game.terminal(&options).to_string();
+                text.lines().map(str::to_string).collect()
+            }
+        };
+
+        queue!(out, cursor::Hide)?;
+        let rows = self.screen.1.saturating_sub(1);
+        for y in 0..rows {
+            queue!(out, cursor::MoveTo(0, y as u16))?;
+            if let Some(line) = lines.get(y) {
+                queue!(out, style::Print(line))?;
+            }
+            queue!(out, terminal::Clear(ClearType::UntilNewLine))?;
+        }
+
+        let status = match &self.message {
+            Some(message) => message.clone(),
+            None => format!(
+                " {} | gen {} | pop {} | {} gen/s | {}x{} {} | cursor {},{} | {:?}",
+                if self.playing { "playing" } else { "paused" },
+                self.game.generation(),
+                self.game.population(),
+                SPEEDS[self.speed],
+                self.game.width(),
+                self.game.height(),
+                self.game.rule(),
+                self.cursor.0,
+                self.cursor.1,
+                self.zoom,
+            ),
+        };
+        let status: String = status.chars().take(self.screen.0).collect();
+        queue!(
+            out,
+            cursor::MoveTo(0, rows as u16),
+            style::SetAttribute(style::Attribute::Reverse),
+            style::Print(format!("{:width$}", status, width = self.screen.0)),
+            style::SetAttribute(style::Attribute::Reset),
+        )?;
+
+        // show the terminal's cursor on the character which contains the cursor cell
+        let (col, row) = self.cursor;
+        if view.contains(col, row) {
+            let (x, y) = (col - self.x, row - self.y);
+            let (x, y) = match self.zoom.cells() {
+                (0, _) => (x * 2, y),
+                (w, h) => (x / w, y / h),
+            };
+            queue!(out, cursor::MoveTo(x as u16, y as u16), cursor::Show)?;
+        }
+
+        out.flush()
+    }
+}
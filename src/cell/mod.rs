@@ -2,6 +2,7 @@
 
 #[macro_use]
 mod macros;
+pub mod pattern;
 
 use core::ops::*;
 pub use Cell::{Alive, Dead};
@@ -0,0 +1,108 @@
+//! Contains the [`life!`](crate::life) macro, and the const functions it uses to check patterns at
+//! compile time. Nothing in here besides the macro is meant to be used directly.
+
+use super::Cell;
+#[cfg(feature = "alloc")]
+use crate::Game;
+
+/// Creates a pattern from string rows of `.` (dead) and `O` (alive), which is checked at compile
+/// time.
+///
+/// `life!["...", ...]` creates a [`Game`](crate::Game) running [`Rule::LIFE`](crate::Rule::LIFE),
+/// and `life!(const ["...", ...])` creates an array of rows of [`Cell`](crate::Cell)s, which can
+/// be used in a `const` without allocating. Each row must be a string literal or a constant.
+///
+/// Empty rows, rows with different lengths, or characters other than `.` and `O`, fail to
+/// compile.
+///
+/// # Examples
+/// ```
+/// # use cgol::{life, Cell::{self, *}};
+/// let mut blinker = life![
+///     ".....",
+///     "..O..",
+///     "..O..",
+///     "..O..",
+///     ".....",
+/// ];
+/// blinker.tick();
+/// assert!(blinker.row(2).eq([Dead, Alive, Alive, Alive, Dead]));
+///
+/// const GLIDER: [[Cell; 3]; 3] = life!(const [".O.", "..O", "OOO"]);
+/// assert_eq!(GLIDER[2], [Alive; 3]);
+/// ```
+///
+/// ```compile_fail
+/// # use cgol::life;
+/// let game = life!["..O", ".O"];
+/// ```
+///
+/// ```compile_fail
+/// # use cgol::life;
+/// let game = life![""];
+/// ```
+#[macro_export]
+macro_rules! life {
+    (const [$($row:expr),+ $(,)?]) => {{
+        const ROWS: &[&str] = &[$($row),+];
+        const WIDTH: usize = $crate::__pattern::width(ROWS);
+        const CELLS: [[$crate::Cell; WIDTH]; ROWS.len()] = $crate::__pattern::cells(ROWS);
+        CELLS
+    }};
+
+    ($($row:expr),+ $(,)?) => {
+        $crate::__pattern::game(&$crate::life!(const [$($row),+]))
+    };
+}
+
+/// Gets the width of every row, failing to compile if they are empty or different.
+pub const fn width(rows: &[&str]) -> usize {
+    let width = rows[0].len();
+    assert!(width != 0, "rows in `life!` must not be empty");
+
+    let mut i = 1;
+    while i < rows.len() {
+        if rows[i].len() != width {
+            panic!("every row in `life!` must have the same length");
+        }
+        i += 1;
+    }
+
+    width
+}
+
+/// Parses every row, failing to compile if there is a character other than `.` and `O`.
+pub const fn cells<const W: usize, const H: usize>(rows: &[&str]) -> [[Cell; W]; H] {
+    let mut cells = [[Cell::Dead; W]; H];
+
+    let mut row = 0;
+    while row < H {
+        let bytes = rows[row].as_bytes();
+
+        let mut col = 0;
+        while col < W {
+            cells[row][col] = match bytes[col] {
+                b'O' => Cell::Alive,
+                b'.' => Cell::Dead,
+                _ => panic!("rows in `life!` must only contain `.` and `O`"),
+            };
+            col += 1;
+        }
+
+        row += 1;
+    }
+
+    cells
+}
+
+/// Creates a game from parsed rows.
+#[cfg(feature = "alloc")]
+pub fn game<const W: usize, const H: usize>(cells: &[[Cell; W]; H]) -> Game {
+    let mut game = Game::new(W, H);
+
+    for (row, cells) in cells.iter().enumerate() {
+        game.get_row_mut(row).copy_from_slice(cells);
+    }
+
+    game
+}
@@ -0,0 +1,320 @@
+//! Contains the [`ColoredGame`] struct, which runs Life with multiple species of cells.
+
+use crate::{cell::Cell, game::panic, rle, Game, Rule};
+use alloc::{string::String, vec, vec::Vec};
+use core::ops::{Index, IndexMut};
+
+/// A variant of Life where every alive cell belongs to a species.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColoredRule {
+    /// 2 species. Newborn cells take the species most of their parents belong to.
+    Immigration,
+    /// 4 species. Newborn cells take the species most of their parents belong to, or the missing
+    /// species if all 3 parents are different.
+    QuadLife,
+}
+
+impl ColoredRule {
+    /// Gets the number of species.
+    #[inline]
+    pub fn species(self) -> u8 {
+        match self {
+            ColoredRule::Immigration => 2,
+            ColoredRule::QuadLife => 4,
+        }
+    }
+
+    /// Gets the name of this rule, as used by Golly.
+    #[inline]
+    pub fn name(self) -> &'static str {
+        match self {
+            ColoredRule::Immigration => "Immigration",
+            ColoredRule::QuadLife => "QuadLife",
+        }
+    }
+}
+
+/// An instance of Life with multiple species, such as Immigration or QuadLife.
+///
+/// It follows the same `B3/S23` dynamics as [`Game`], but each cell is stored as a state: `0` is
+/// dead, and `1` up to [`ColoredRule::species`] are the species of alive cells. Surviving cells
+/// keep their species. Setting a cell to any other state is a logic error, and
+/// [`tick`](ColoredGame::tick) may panic or produce meaningless states.
+///
+/// # Examples
+/// ```
+/// # use cgol::colored::{ColoredGame, ColoredRule};
+/// let mut game = ColoredGame::new(5, 5, ColoredRule::Immigration);
+///
+/// // a blinker with 2 cells of species 1, and 1 cell of species 2
+/// game[(1, 2)] = 1;
+/// game[(2, 2)] = 2;
+/// game[(3, 2)] = 1;
+///
+/// game.tick();
+/// assert_eq!(game.col(2).collect::<Vec<_>>(), &[0, 1, 2, 1, 0]);
+/// assert_eq!(game.population(1), 2);
+/// assert_eq!(game.population(2), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColoredGame {
+    cells: Vec<u8>,
+    next: Vec<u8>,
+    width: usize,
+    height: usize,
+    rule: ColoredRule,
+}
+
+impl ColoredGame {
+    /// Creates a new instance, where every cell is dead.
+    ///
+    /// # Panics
+    /// Panics if `width < 1`, `height < 1`, or if `width * height > isize::MAX`.
+    pub fn new(width: usize, height: usize, rule: ColoredRule) -> ColoredGame {
+        if width == 0 {
+            panic::width_is_zero();
+        } else if height == 0 {
+            panic::height_is_zero();
+        }
+
+        // doesn't need documentation since it's already > isize::MAX
+        let area = width.checked_mul(height).expect("width * height overflow");
+        let cells = vec![0; area];
+        let next = cells.clone();
+
+        Self {
+            cells,
+            next,
+            width,
+            height,
+            rule,
+        }
+    }
+
+    /// Creates a new instance from a [`Game`], where every alive cell belongs to species `1`.
+    pub fn from_game(game: &Game, rule: ColoredRule) -> ColoredGame {
+        let mut colored = ColoredGame::new(game.width(), game.height(), rule);
+        for row in 0..game.height() {
+            for (cell, alive) in colored.get_row_mut(row).iter_mut().zip(game.row(row)) {
+                *cell = (alive == Cell::Alive) as u8;
+            }
+        }
+
+        colored
+    }
+
+    /// Ticks once.
+    pub fn tick(&mut self) {
+        let row_max = self.height - 1;
+        let col_max = self.width - 1;
+        let cells = &self.cells;
+
+        for row in 0..self.height {
+            let up = if row == 0 { row_max } else { row - 1 } * self.width;
+            let down = if row == row_max { 0 } else { row + 1 } * self.width;
+            let row = row * self.width;
+
+            for col in 0..self.width {
+                let left = if col == 0 { col_max } else { col - 1 };
+                let right = if col == col_max { 0 } else { col + 1 };
+
+                // how many neighbors belong to each species, with dead cells at index 0
+                let mut counts = [0u8; 5];
+                for &neighbor in &[
+                    up + left,
+                    up + col,
+                    up + right,
+                    row + right,
+                    down + right,
+                    down + col,
+                    down + left,
+                    row + left,
+                ] {
+                    counts[cells[neighbor] as usize] += 1;
+                }
+
+                let cell = cells[row + col];
+                let alive = 8 - counts[0];
+                self.next[row + col] = if cell != 0 {
+                    if Rule::LIFE.survives(alive) {
+                        cell
+                    } else {
+                        0
+                    }
+                } else if Rule::LIFE.born(alive) {
+                    self.newborn(&counts)
+                } else {
+                    0
+                };
+            }
+        }
+
+        core::mem::swap(&mut self.cells, &mut self.next);
+    }
+
+    /// Decides the species of a newborn cell, given how many parents belong to each species.
+    fn newborn(&self, counts: &[u8; 5]) -> u8 {
+        let species = 1..=self.rule.species();
+
+        // with 3 parents, at most one species can have 2 or more
+        match species.clone().find(|&s| counts[s as usize] >= 2) {
+            Some(s) => s,
+            None => species
+                .clone()
+                .find(|&s| counts[s as usize] == 0)
+                .unwrap_or(1),
+        }
+    }
+
+    /// Gets the rule this game is running.
+    #[inline]
+    pub fn rule(&self) -> ColoredRule {
+        self.rule
+    }
+
+    /// Gets this game's width.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets this game's height.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets a specific cell, returning `None` if out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        if row < self.height && col < self.width {
+            Some(self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Mutably gets a specific cell, returning `None` if out of bounds.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut u8> {
+        if row < self.height && col < self.width {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Gets a slice of a specific row.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn get_row(&self, row: usize) -> &[u8] {
+        if row >= self.height {
+            panic::height(row, self.height);
+        }
+
+        let begin = row * self.width;
+        &self.cells[begin..begin + self.width]
+    }
+
+    /// Gets a mutable slice of a specific row.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn get_row_mut(&mut self, row: usize) -> &mut [u8] {
+        if row >= self.height {
+            panic::height(row, self.height);
+        }
+
+        let begin = row * self.width;
+        &mut self.cells[begin..begin + self.width]
+    }
+
+    /// Gets an iterator over the cells in a specific column.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn col(&self, col: usize) -> impl Iterator<Item = u8> + '_ {
+        if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        self.cells.iter().copied().skip(col).step_by(self.width)
+    }
+
+    /// Counts the alive cells belonging to a species. Passing `0` counts the dead cells instead.
+    pub fn population(&self, species: u8) -> usize {
+        self.cells.iter().filter(|&&c| c == species).count()
+    }
+
+    /// Counts every alive cell, regardless of species.
+    pub fn total_population(&self) -> usize {
+        self.cells.iter().filter(|&&c| c != 0).count()
+    }
+
+    /// Converts this into a [`Game`], forgetting the species of each cell.
+    pub fn to_game(&self) -> Game {
+        let mut game = Game::new(self.width, self.height);
+
+        for row in 0..self.height {
+            for (cell, &state) in game.get_row_mut(row).iter_mut().zip(self.get_row(row)) {
+                *cell = Cell::from(state);
+            }
+        }
+
+        game
+    }
+
+    /// Kills all cells.
+    pub fn clear(&mut self) {
+        self.cells.fill(0);
+    }
+
+    /// Writes this game as Golly's multi-state RLE, where species `1` is `A`, species `2` is `B`,
+    /// and so on.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::colored::{ColoredGame, ColoredRule};
+    /// let mut game = ColoredGame::new(4, 3, ColoredRule::QuadLife);
+    /// game[(0, 0)] = 1;
+    /// game[(1, 0)] = 1;
+    /// game[(3, 0)] = 4;
+    /// game[(2, 2)] = 2;
+    ///
+    /// assert_eq!(game.to_rle(), "x = 4, y = 3, rule = QuadLife\n2A.D2$2.B!\n");
+    /// ```
+    pub fn to_rle(&self) -> String {
+        rle::encode(
+            self.width,
+            self.height,
+            self.rule.name(),
+            true,
+            |row, col| self.cells[row * self.width + col],
+        )
+    }
+}
+
+impl Index<(usize, usize)> for ColoredGame {
+    type Output = u8;
+
+    fn index(&self, (col, row): (usize, usize)) -> &u8 {
+        if row >= self.height {
+            panic::height(row, self.height);
+        } else if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for ColoredGame {
+    fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut u8 {
+        if row >= self.height {
+            panic::height(row, self.height);
+        } else if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        &mut self.cells[row * self.width + col]
+    }
+}
@@ -0,0 +1,235 @@
+//! Contains the [`Elementary`] struct, which runs Wolfram's elementary cellular automata.
+
+use crate::{cell::Cell, game::panic, Game};
+use alloc::{vec, vec::Vec};
+use core::ops::Index;
+
+/// What lies beyond the edges of an [`Elementary`] automaton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Boundary {
+    /// The edges wrap around, like in [`Game`].
+    Wrap,
+    /// The edges are surrounded by cells which never change.
+    Fixed(Cell),
+}
+
+impl Default for Boundary {
+    #[inline]
+    fn default() -> Self {
+        Boundary::Wrap
+    }
+}
+
+/// A one-dimensional elementary cellular automaton, such as rule 30 or rule 110.
+///
+/// Each cell's next state depends on itself and its left and right neighbors. Reading those three
+/// cells as a binary number from left to right (so `111` is `7`), the cell becomes alive if that
+/// bit of the rule number is set. Cells are bit-packed, and every row of 64 cells is stepped at
+/// once.
+///
+/// # Examples
+/// ```
+/// # use cgol::{elementary::Elementary, Cell::*};
+/// let mut rule90 = Elementary::new(5, 90);
+/// rule90.set(2, Alive);
+///
+/// rule90.tick();
+/// assert_eq!(rule90.iter().collect::<Vec<_>>(), &[Dead, Alive, Dead, Alive, Dead]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Elementary {
+    /// Bit `i % 64` of word `i / 64` is cell `i`. Bits past the width are always `0`.
+    words: Vec<u64>,
+    width: usize,
+    rule: u8,
+    boundary: Boundary,
+}
+
+impl Elementary {
+    /// Creates a new automaton with wrapping edges, where every cell is dead.
+    ///
+    /// # Panics
+    /// Panics if `width < 1`.
+    pub fn new(width: usize, rule: u8) -> Elementary {
+        Elementary::with_boundary(width, rule, Boundary::Wrap)
+    }
+
+    /// Creates a new automaton with a specific boundary, where every cell is dead.
+    ///
+    /// # Panics
+    /// Panics if `width < 1`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{elementary::{Boundary, Elementary}, Cell::*};
+    /// // rule 1 turns on cells surrounded by dead cells
+    /// let mut wrapping = Elementary::new(3, 1);
+    /// let mut fixed = Elementary::with_boundary(3, 1, Boundary::Fixed(Alive));
+    /// wrapping.tick();
+    /// fixed.tick();
+    ///
+    /// assert_eq!(wrapping.iter().collect::<Vec<_>>(), &[Alive, Alive, Alive]);
+    /// assert_eq!(fixed.iter().collect::<Vec<_>>(), &[Dead, Alive, Dead]);
+    /// ```
+    pub fn with_boundary(width: usize, rule: u8, boundary: Boundary) -> Elementary {
+        if width == 0 {
+            panic::width_is_zero();
+        }
+
+        Self {
+            words: vec![0; (width - 1) / 64 + 1],
+            width,
+            rule,
+            boundary,
+        }
+    }
+
+    /// Gets this automaton's rule number.
+    #[inline]
+    pub fn rule(&self) -> u8 {
+        self.rule
+    }
+
+    /// Gets this automaton's boundary.
+    #[inline]
+    pub fn boundary(&self) -> Boundary {
+        self.boundary
+    }
+
+    /// Gets this automaton's width.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets a specific cell, returning `None` if out of bounds.
+    pub fn get(&self, col: usize) -> Option<Cell> {
+        if col < self.width {
+            Some(Cell::from(self.words[col / 64] >> (col % 64) & 1 != 0))
+        } else {
+            None
+        }
+    }
+
+    /// Sets a specific cell.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn set(&mut self, col: usize, cell: Cell) {
+        if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        let word = &mut self.words[col / 64];
+        let bit = 1 << (col % 64);
+        match cell {
+            Cell::Alive => *word |= bit,
+            Cell::Dead => *word &= !bit,
+        }
+    }
+
+    /// Gets an iterator over the cells, from left to right.
+    pub fn iter(&self) -> impl Iterator<Item = Cell> + '_ {
+        (0..self.width).map(move |col| self[col])
+    }
+
+    /// Counts the alive cells.
+    pub fn population(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Kills all cells.
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Ticks once.
+    pub fn tick(&mut self) {
+        let last = self.width - 1;
+        let (outside_left, outside_right) = match self.boundary {
+            Boundary::Wrap => (self[last], self[0]),
+            Boundary::Fixed(cell) => (cell, cell),
+        };
+
+        let mut carry = outside_left as u64;
+        let mut next = Vec::with_capacity(self.words.len());
+
+        for (i, &center) in self.words.iter().enumerate() {
+            // the cells to the left and right of each cell in this word
+            let left = center << 1 | carry;
+            let mut right = center >> 1 | self.words.get(i + 1).map_or(0, |w| w << 63);
+            if i == last / 64 {
+                // bits past the width are 0, so this is the only bit that needs to be set
+                right |= (outside_right as u64) << (last % 64);
+            }
+
+            carry = center >> 63;
+            next.push(self.apply(left, center, right));
+        }
+
+        // keep bits past the width at 0
+        next[last / 64] &= u64::MAX >> (63 - last % 64);
+
+        self.words = next;
+    }
+
+    /// Applies the rule to 64 cells at once.
+    #[inline]
+    fn apply(&self, left: u64, center: u64, right: u64) -> u64 {
+        (0..8)
+            .filter(|pattern| self.rule >> pattern & 1 != 0)
+            .fold(0, |next, pattern| {
+                let pick = |bit: u8, x: u64| if pattern >> bit & 1 != 0 { x } else { !x };
+                next | pick(2, left) & pick(1, center) & pick(0, right)
+            })
+    }
+
+    /// Records a space-time diagram into a [`Game`], one generation per row.
+    ///
+    /// The first row is the current generation, and this automaton is ticked after each row. That
+    /// way, calling this again continues where the last diagram ended.
+    ///
+    /// # Panics
+    /// Panics if `generations < 1`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{elementary::Elementary, Cell::*};
+    /// let mut rule90 = Elementary::new(7, 90);
+    /// rule90.set(3, Alive);
+    ///
+    /// let diagram = rule90.history(4);
+    /// assert_eq!(diagram.get_row(0), &[Dead, Dead, Dead, Alive, Dead, Dead, Dead]);
+    /// assert_eq!(diagram.get_row(1), &[Dead, Dead, Alive, Dead, Alive, Dead, Dead]);
+    /// assert_eq!(diagram.get_row(2), &[Dead, Alive, Dead, Dead, Dead, Alive, Dead]);
+    /// assert_eq!(diagram.get_row(3), &[Alive, Dead, Alive, Dead, Alive, Dead, Alive]);
+    /// ```
+    pub fn history(&mut self, generations: usize) -> Game {
+        let mut game = Game::new(self.width, generations);
+
+        for row in 0..generations {
+            for (cell, new) in game.row_mut(row).zip(self.iter()) {
+                *cell = new;
+            }
+            self.tick();
+        }
+
+        game
+    }
+}
+
+impl Index<usize> for Elementary {
+    type Output = Cell;
+
+    fn index(&self, col: usize) -> &Cell {
+        if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        if self.words[col / 64] >> (col % 64) & 1 != 0 {
+            &Cell::Alive
+        } else {
+            &Cell::Dead
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Contains [`Game`]'s optional activity layer, which keeps track of each cell's age and heat.
+
+use super::{panic, Game};
+use crate::cell::Cell;
+use alloc::{vec, vec::Vec};
+
+/// Per-cell counters, stored in the same order as the cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Activity {
+    /// How many generations in a row the cell has been alive, or `0` if it's dead.
+    age: Vec<u16>,
+    /// How many times the cell has been born or died.
+    heat: Vec<u16>,
+}
+
+impl Game {
+    /// Starts keeping track of each cell's age and heat.
+    ///
+    /// A cell's age is how many generations in a row it has been alive, counting the current one,
+    /// or `0` if it's dead. Its heat is how many times it has been born or died. Both are updated
+    /// by every kind of tick, and stop counting once they reach [`u16::MAX`]. Changes made to the
+    /// cells between ticks are counted too.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(5, 5);
+    /// game.enable_activity();
+    ///
+    /// // a blinker
+    /// game[(1, 2)] = Alive;
+    /// game[(2, 2)] = Alive;
+    /// game[(3, 2)] = Alive;
+    /// game.tick();
+    /// game.tick();
+    ///
+    /// // the center never changes, and the others flip every tick
+    /// assert_eq!(game.age(2, 2), Some(3));
+    /// assert_eq!(game.heat(2, 2), Some(1));
+    /// assert_eq!(game.age_row(2), Some(&[0, 1, 3, 1, 0][..]));
+    /// assert_eq!(game.heat_row(2), Some(&[0, 3, 1, 3, 0][..]));
+    /// ```
+    pub fn enable_activity(&mut self) {
+        if self.activity.is_none() {
+            // cells which are alive right now start at age 1, without being counted as births
+            let age = (0..self.cells.len())
+                .map(|index| self.is_alive(index) as u16)
+                .collect();
+            let heat = vec![0; self.cells.len()];
+            self.activity = Some(Activity { age, heat });
+        }
+    }
+
+    /// Stops keeping track of each cell's age and heat.
+    pub fn disable_activity(&mut self) {
+        self.activity = None;
+    }
+
+    /// Returns `true` if each cell's age and heat are being kept track of.
+    #[inline]
+    pub fn has_activity(&self) -> bool {
+        self.activity.is_some()
+    }
+
+    /// Sets every cell's heat to `0`. Ages are kept.
+    pub fn reset_heat(&mut self) {
+        if let Some(activity) = &mut self.activity {
+            activity.heat.fill(0);
+        }
+    }
+
+    /// Gets a specific cell's age, returning `None` if it is out of bounds or activity isn't being
+    /// kept track of.
+    pub fn age(&self, row: usize, col: usize) -> Option<u16> {
+        let activity = self.activity.as_ref()?;
+
+        if row < self.height && col < self.width {
+            Some(activity.age[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Gets a specific cell's heat, returning `None` if it is out of bounds or activity isn't
+    /// being kept track of.
+    pub fn heat(&self, row: usize, col: usize) -> Option<u16> {
+        let activity = self.activity.as_ref()?;
+
+        if row < self.height && col < self.width {
+            Some(activity.heat[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    /// Gets a slice of the ages in a specific row, returning `None` if activity isn't being kept
+    /// track of.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn age_row(&self, row: usize) -> Option<&[u16]> {
+        let begin = self.activity_row(row);
+        self.activity
+            .as_ref()
+            .map(|activity| &activity.age[begin..begin + self.width])
+    }
+
+    /// Gets a slice of the heats in a specific row, returning `None` if activity isn't being kept
+    /// track of.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn heat_row(&self, row: usize) -> Option<&[u16]> {
+        let begin = self.activity_row(row);
+        self.activity
+            .as_ref()
+            .map(|activity| &activity.heat[begin..begin + self.width])
+    }
+
+    /// Gets an iterator over the ages in a specific column, returning `None` if activity isn't
+    /// being kept track of.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn age_col(&self, col: usize) -> Option<impl Iterator<Item = u16> + '_> {
+        if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        let width = self.width;
+        self.activity
+            .as_ref()
+            .map(move |activity| activity.age.iter().copied().skip(col).step_by(width))
+    }
+
+    /// Gets an iterator over the heats in a specific column, returning `None` if activity isn't
+    /// being kept track of.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn heat_col(&self, col: usize) -> Option<impl Iterator<Item = u16> + '_> {
+        if col >= self.width {
+            panic::width(col, self.width);
+        }
+
+        let width = self.width;
+        self.activity
+            .as_ref()
+            .map(move |activity| activity.heat.iter().copied().skip(col).step_by(width))
+    }
+
+    /// Checks that a row is in bounds, and gets the index of its first cell.
+    fn activity_row(&self, row: usize) -> usize {
+        if row >= self.height {
+            panic::height(row, self.height);
+        }
+
+        row * self.width
+    }
+
+    /// Updates each cell's age and heat.
+    ///
+    /// A cell is alive at the last update if its age isn't `0`, so changes can be found without
+    /// the previous generation. With `new_generation`, alive cells which were already alive get
+    /// older. Otherwise, only changes made since the last update are counted.
+    pub(super) fn record_activity(&mut self, new_generation: bool) {
+        let activity = match &mut self.activity {
+            Some(activity) => activity,
+            None => return,
+        };
+
+        let inverted = self.inverted;
+        let cells = self
+            .cells
+            .iter()
+            .zip(&mut activity.age)
+            .zip(&mut activity.heat);
+
+        for ((&cell, age), heat) in cells {
+            let alive = (cell == Cell::Alive) ^ inverted;
+
+            match (*age != 0, alive) {
+                (true, true) if new_generation => *age = age.saturating_add(1),
+                (true, true) | (false, false) => {}
+                (was_alive, _) => {
+                    *age = if was_alive { 0 } else { 1 };
+                    *heat = heat.saturating_add(1);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,101 @@
+//! Contains [`Diff`], which compares a [`Game`]'s current and previous generations.
+
+use super::Game;
+use crate::cell::Cell;
+
+/// The changes between a [`Game`]'s previous and current generations.
+///
+/// This borrows the game's two buffers, so creating one doesn't copy the board. It is created by
+/// the [`Game::diff`] method.
+#[derive(Debug, Clone, Copy)]
+pub struct Diff<'a> {
+    current: &'a [Cell],
+    previous: &'a [Cell],
+    width: usize,
+    inverted: bool,
+    prev_inverted: bool,
+}
+
+impl<'a> Diff<'a> {
+    /// Gets an iterator over the `(row, col)` of every cell which was born, in row-major order.
+    pub fn births(&self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.changes(true)
+    }
+
+    /// Gets an iterator over the `(row, col)` of every cell which died, in row-major order.
+    pub fn deaths(&self) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.changes(false)
+    }
+
+    /// Counts the cells which were born.
+    pub fn birth_count(&self) -> usize {
+        self.births().count()
+    }
+
+    /// Counts the cells which died.
+    pub fn death_count(&self) -> usize {
+        self.deaths().count()
+    }
+
+    /// Returns `true` if no cells were born or died.
+    pub fn is_empty(&self) -> bool {
+        self.births().next().is_none() && self.deaths().next().is_none()
+    }
+
+    /// Gets the cells which are alive in the current generation and dead in the previous one, or
+    /// the other way around without `born`.
+    fn changes(&self, born: bool) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let Diff {
+            current,
+            previous,
+            width,
+            inverted,
+            prev_inverted,
+        } = *self;
+
+        current
+            .iter()
+            .zip(previous)
+            .enumerate()
+            .filter(move |&(_, (&now, &before))| {
+                let alive = (now == Cell::Alive) ^ inverted;
+                let was_alive = (before == Cell::Alive) ^ prev_inverted;
+                alive == born && was_alive != born
+            })
+            .map(move |(index, _)| (index / width, index % width))
+    }
+}
+
+impl Game {
+    /// Compares the current generation to the previous one, which is kept from the last tick.
+    ///
+    /// Changes made to the cells since the last tick are included. Before the first tick, the
+    /// previous generation is empty. After [`untick`](Game::untick), the previous generation is
+    /// the one [`tick_reversible`](Game::tick_reversible) would have used.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(5, 5);
+    ///
+    /// // a blinker
+    /// game[(1, 2)] = Alive;
+    /// game[(2, 2)] = Alive;
+    /// game[(3, 2)] = Alive;
+    /// game.tick();
+    ///
+    /// let diff = game.diff();
+    /// assert_eq!(diff.births().collect::<Vec<_>>(), &[(1, 2), (3, 2)]);
+    /// assert_eq!(diff.deaths().collect::<Vec<_>>(), &[(2, 1), (2, 3)]);
+    /// assert_eq!(diff.birth_count(), 2);
+    /// ```
+    pub fn diff(&self) -> Diff<'_> {
+        Diff {
+            current: &self.cells,
+            previous: &self.next,
+            width: self.width,
+            inverted: self.inverted,
+            prev_inverted: self.prev_inverted,
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Contains [`Game`]'s optional history layer, which works like Golly's LifeHistory rule.
+
+use super::Game;
+use crate::{cell::Cell, rle};
+use alloc::{string::String, vec};
+
+/// The cell has been alive at some point.
+const EVER_ALIVE: u8 = 1 << 0;
+/// The cell was marked by the user.
+const MARKED: u8 = 1 << 1;
+
+impl Game {
+    /// Starts recording which cells have ever been alive. Cells which are alive right now count
+    /// as having been alive.
+    ///
+    /// The history is updated by every kind of tick, until it is
+    /// [disabled](Game::disable_history).
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(5, 5);
+    /// game.enable_history();
+    ///
+    /// // a blinker
+    /// game[(1, 2)] = Alive;
+    /// game[(2, 2)] = Alive;
+    /// game[(3, 2)] = Alive;
+    /// game.tick();
+    ///
+    /// // the blinker's envelope is a plus sign
+    /// assert_eq!(game.ever_alive(2, 1), Some(true));
+    /// assert_eq!(game.ever_alive(1, 2), Some(true));
+    /// assert_eq!(game.ever_alive(1, 1), Some(false));
+    /// ```
+    pub fn enable_history(&mut self) {
+        if self.history.is_none() {
+            self.history = Some(vec![0; self.cells.len()]);
+            self.record_history();
+        }
+    }
+
+    /// Stops recording which cells have ever been alive, and forgets every mark.
+    pub fn disable_history(&mut self) {
+        self.history = None;
+    }
+
+    /// Returns `true` if the history is being recorded.
+    #[inline]
+    pub fn has_history(&self) -> bool {
+        self.history.is_some()
+    }
+
+    /// Forgets which cells have been alive, except for the ones which are alive right now. Marks
+    /// are kept.
+    pub fn reset_history(&mut self) {
+        if let Some(history) = &mut self.history {
+            for flags in history.iter_mut() {
+                *flags &= !EVER_ALIVE;
+            }
+        }
+
+        self.record_history();
+    }
+
+    /// Returns whether a specific cell has ever been alive since the history was enabled, or
+    /// `None` if it is out of bounds or the history is disabled.
+    pub fn ever_alive(&self, row: usize, col: usize) -> Option<bool> {
+        let history = self.history.as_ref()?;
+
+        if row < self.height && col < self.width {
+            let index = row * self.width + col;
+            Some(history[index] & EVER_ALIVE != 0 || self.is_alive(index))
+        } else {
+            None
+        }
+    }
+
+    /// Returns whether a specific cell is marked, or `None` if it is out of bounds or the history
+    /// is disabled.
+    pub fn is_marked(&self, row: usize, col: usize) -> Option<bool> {
+        let history = self.history.as_ref()?;
+
+        if row < self.height && col < self.width {
+            Some(history[row * self.width + col] & MARKED != 0)
+        } else {
+            None
+        }
+    }
+
+    /// Marks or unmarks a specific cell, enabling the history if it's disabled.
+    ///
+    /// Marks don't affect the game. They are useful for annotating cells, such as ones which must
+    /// stay dead, and are exported by [`to_life_history_rle`](Game::to_life_history_rle).
+    ///
+    /// # Panics
+    /// Panics if `row` or `col` are out of bounds.
+    pub fn set_marked(&mut self, row: usize, col: usize, marked: bool) {
+        if row >= self.height {
+            super::panic::height(row, self.height);
+        } else if col >= self.width {
+            super::panic::width(col, self.width);
+        }
+
+        self.enable_history();
+        if let Some(history) = &mut self.history {
+            let flags = &mut history[row * self.width + col];
+            if marked {
+                *flags |= MARKED;
+            } else {
+                *flags &= !MARKED;
+            }
+        }
+    }
+
+    /// Writes this game as RLE for Golly's LifeHistory rule.
+    ///
+    /// Alive cells are state `1`, or `3` if they are marked. Dead cells are state `4` if they are
+    /// marked, `2` if they have been alive before, and `0` otherwise. Without a history, this is
+    /// the same as normal RLE with different letters.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(3, 3);
+    /// game.enable_history();
+    /// game.set_marked(0, 2, true);
+    ///
+    /// game[(0, 0)] = Alive;
+    /// game.tick();
+    /// game[(1, 0)] = Alive;
+    ///
+    /// assert_eq!(game.to_life_history_rle(), "x = 3, y = 3, rule = LifeHistory\nBAD!\n");
+    /// ```
+    pub fn to_life_history_rle(&self) -> String {
+        rle::encode(self.width, self.height, "LifeHistory", true, |row, col| {
+            let index = row * self.width + col;
+            let flags = self.history.as_ref().map_or(0, |history| history[index]);
+
+            match (self.is_alive(index), flags & MARKED != 0) {
+                (true, false) => 1,
+                (true, true) => 3,
+                (false, true) => 4,
+                (false, false) if flags & EVER_ALIVE != 0 => 2,
+                (false, false) => 0,
+            }
+        })
+    }
+
+    /// Adds the cells which are alive right now to the history.
+    pub(super) fn record_history(&mut self) {
+        let history = match &mut self.history {
+            Some(history) => history,
+            None => return,
+        };
+
+        let alive = if self.inverted {
+            Cell::Dead
+        } else {
+            Cell::Alive
+        };
+        for (flags, &cell) in history.iter_mut().zip(&self.cells) {
+            if cell == alive {
+                *flags |= EVER_ALIVE;
+            }
+        }
+    }
+}
@@ -1,32 +1,437 @@
 //! Contains [`Game`]'s iterators.
 
-use crate::cell::Cell;
+use crate::{cell::Cell, Rect};
 use core::{
-    iter::{Copied, Skip, StepBy},
-    slice::{Iter, IterMut},
+    iter::{Enumerate, FusedIterator, StepBy},
+    slice::{ChunksExact, Iter, IterMut},
 };
 
 #[cfg(doc)]
 use crate::game::Game;
 
-// TODO: make these newtypes
+/// Implements [`Iterator`], [`DoubleEndedIterator`], [`ExactSizeIterator`] and [`FusedIterator`]
+/// for a newtype by forwarding to the iterator in its first field, after converting each item
+/// with its `item` method.
+macro_rules! forward_iterator {
+    ($name:ident, $item:ty) => {
+        impl<'a> Iterator for $name<'a> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let item = self.0.next()?;
+                Some(self.item(item))
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                let item = self.0.nth(n)?;
+                Some(self.item(item))
+            }
+        }
+
+        impl<'a> DoubleEndedIterator for $name<'a> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let item = self.0.next_back()?;
+                Some(self.item(item))
+            }
+
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                let item = self.0.nth_back(n)?;
+                Some(self.item(item))
+            }
+        }
+
+        impl ExactSizeIterator for $name<'_> {
+            #[inline]
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        impl FusedIterator for $name<'_> {}
+    };
+}
+
+/// Converts a stored cell to its logical state.
+#[inline]
+fn logical(cell: Cell, inverted: bool) -> Cell {
+    if inverted {
+        !cell
+    } else {
+        cell
+    }
+}
 
 /// An iterator over the cells in a specific row.
 ///
 /// This is created by the [`Game::row`] method.
-pub type Row<'a> = Copied<Iter<'a, Cell>>;
+#[derive(Debug, Clone)]
+pub struct Row<'a>(Iter<'a, Cell>, bool);
+
+impl<'a> Row<'a> {
+    /// Creates an iterator over a row's stored cells, which yields their logical state.
+    pub(crate) fn new(cells: &'a [Cell], inverted: bool) -> Self {
+        Row(cells.iter(), inverted)
+    }
+
+    #[inline]
+    fn item(&self, &cell: &Cell) -> Cell {
+        logical(cell, self.1)
+    }
+}
+
+forward_iterator!(Row, Cell);
 
 /// A mutable iterator over the cells in a specific row.
 ///
 /// This is created by the [`Game::row_mut`] method.
-pub type RowMut<'a> = IterMut<'a, Cell>;
+#[derive(Debug)]
+pub struct RowMut<'a>(IterMut<'a, Cell>);
+
+impl<'a> RowMut<'a> {
+    /// Creates an iterator over a row's stored cells, which must not be inverted.
+    pub(crate) fn new(cells: &'a mut [Cell]) -> Self {
+        RowMut(cells.iter_mut())
+    }
+
+    #[inline]
+    fn item(&self, cell: &'a mut Cell) -> &'a mut Cell {
+        cell
+    }
+}
+
+forward_iterator!(RowMut, &'a mut Cell);
 
 /// An iterator over the cells in a specific column.
 ///
 /// This is created by the [`Game::col`] method.
-pub type Col<'a> = StepBy<Skip<Copied<Iter<'a, Cell>>>>;
+#[derive(Debug, Clone)]
+pub struct Col<'a>(StepBy<Iter<'a, Cell>>, bool);
+
+impl<'a> Col<'a> {
+    /// Creates an iterator over a column, given the stored cells starting at its top cell, which
+    /// yields their logical state.
+    pub(crate) fn new(cells: &'a [Cell], width: usize, inverted: bool) -> Self {
+        Col(cells.iter().step_by(width), inverted)
+    }
+
+    #[inline]
+    fn item(&self, &cell: &Cell) -> Cell {
+        logical(cell, self.1)
+    }
+}
+
+forward_iterator!(Col, Cell);
 
 /// A mutable iterator over the cells in a specific column.
 ///
 /// This is created by the [`Game::col_mut`] method.
-pub type ColMut<'a> = StepBy<Skip<IterMut<'a, Cell>>>;
+#[derive(Debug)]
+pub struct ColMut<'a>(StepBy<IterMut<'a, Cell>>);
+
+impl<'a> ColMut<'a> {
+    /// Creates an iterator over a column, given the stored cells starting at its top cell, which
+    /// must not be inverted.
+    pub(crate) fn new(cells: &'a mut [Cell], width: usize) -> Self {
+        ColMut(cells.iter_mut().step_by(width))
+    }
+
+    #[inline]
+    fn item(&self, cell: &'a mut Cell) -> &'a mut Cell {
+        cell
+    }
+}
+
+forward_iterator!(ColMut, &'a mut Cell);
+
+/// An iterator over every row, from top to bottom.
+///
+/// This is created by the [`Game::rows`] method.
+#[derive(Debug, Clone)]
+pub struct Rows<'a>(ChunksExact<'a, Cell>, bool);
+
+impl<'a> Rows<'a> {
+    pub(crate) fn new(cells: &'a [Cell], width: usize, inverted: bool) -> Self {
+        Rows(cells.chunks_exact(width), inverted)
+    }
+
+    #[inline]
+    fn item(&self, cells: &'a [Cell]) -> Row<'a> {
+        Row::new(cells, self.1)
+    }
+}
+
+forward_iterator!(Rows, Row<'a>);
+
+/// An iterator over every column, from left to right.
+///
+/// This is created by the [`Game::cols`] method.
+#[derive(Debug, Clone)]
+pub struct Cols<'a> {
+    cells: &'a [Cell],
+    width: usize,
+    inverted: bool,
+    /// The next column from the front.
+    front: usize,
+    /// The column after the next one from the back.
+    back: usize,
+}
+
+impl<'a> Cols<'a> {
+    pub(crate) fn new(cells: &'a [Cell], width: usize, inverted: bool) -> Self {
+        Cols {
+            cells,
+            width,
+            inverted,
+            front: 0,
+            back: width,
+        }
+    }
+}
+
+impl<'a> Iterator for Cols<'a> {
+    type Item = Col<'a>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(Col::new(
+            &self.cells[self.front - 1..],
+            self.width,
+            self.inverted,
+        ))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Cols<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(Col::new(
+            &self.cells[self.back..],
+            self.width,
+            self.inverted,
+        ))
+    }
+}
+
+impl ExactSizeIterator for Cols<'_> {}
+
+impl FusedIterator for Cols<'_> {}
+
+/// An iterator over the positions of alive cells, as `(row, col)`, in row-major order.
+///
+/// This is created by the [`Game::alive_cells`] method.
+#[derive(Debug, Clone)]
+pub struct AliveCells<'a> {
+    /// The cells which haven't been searched yet.
+    cells: &'a [Cell],
+    /// The index of the first cell in `cells`.
+    offset: usize,
+    width: usize,
+    /// How alive cells are stored, which is [`Cell::Dead`] if the game is inverted.
+    alive: Cell,
+}
+
+impl<'a> AliveCells<'a> {
+    pub(crate) fn new(cells: &'a [Cell], width: usize, inverted: bool) -> Self {
+        AliveCells {
+            cells,
+            offset: 0,
+            width,
+            alive: if inverted { Cell::Dead } else { Cell::Alive },
+        }
+    }
+
+    #[inline]
+    fn coords(&self, i: usize) -> (usize, usize) {
+        let index = self.offset + i;
+        (index / self.width, index % self.width)
+    }
+}
+
+impl Iterator for AliveCells<'_> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let alive = self.alive;
+        let i = self.cells.iter().position(|&cell| cell == alive)?;
+        let coords = self.coords(i);
+
+        self.cells = &self.cells[i + 1..];
+        self.offset += i + 1;
+        Some(coords)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.cells.len()))
+    }
+}
+
+impl DoubleEndedIterator for AliveCells<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let alive = self.alive;
+        let i = self.cells.iter().rposition(|&cell| cell == alive)?;
+        let coords = self.coords(i);
+
+        self.cells = &self.cells[..i];
+        Some(coords)
+    }
+}
+
+impl FusedIterator for AliveCells<'_> {}
+
+/// An iterator over every cell along with its position, as `((row, col), cell)`, in row-major
+/// order.
+///
+/// This is created by the [`Game::cells_with_coords`] method.
+#[derive(Debug, Clone)]
+pub struct CellsWithCoords<'a> {
+    cells: Enumerate<Iter<'a, Cell>>,
+    width: usize,
+    inverted: bool,
+}
+
+impl<'a> CellsWithCoords<'a> {
+    /// Creates an iterator over the stored cells, which yields their logical state.
+    pub(crate) fn new(cells: &'a [Cell], width: usize, inverted: bool) -> Self {
+        CellsWithCoords {
+            cells: cells.iter().enumerate(),
+            width,
+            inverted,
+        }
+    }
+
+    #[inline]
+    fn item(&self, (i, &cell): (usize, &Cell)) -> ((usize, usize), Cell) {
+        (
+            (i / self.width, i % self.width),
+            logical(cell, self.inverted),
+        )
+    }
+}
+
+impl Iterator for CellsWithCoords<'_> {
+    type Item = ((usize, usize), Cell);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.cells.next()?;
+        Some(self.item(item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.cells.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for CellsWithCoords<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let item = self.cells.next_back()?;
+        Some(self.item(item))
+    }
+}
+
+impl ExactSizeIterator for CellsWithCoords<'_> {}
+
+impl FusedIterator for CellsWithCoords<'_> {}
+
+/// An iterator over the cells in a rectangle along with their positions, as `((row, col), cell)`,
+/// in row-major order.
+///
+/// This is created by the [`Game::cells_in_rect`] method.
+#[derive(Debug, Clone)]
+pub struct CellsInRect<'a> {
+    cells: &'a [Cell],
+    width: usize,
+    inverted: bool,
+    rect: Rect,
+    /// The index in `rect` of the next cell from the front.
+    front: usize,
+    /// The index in `rect` after the next cell from the back.
+    back: usize,
+}
+
+impl<'a> CellsInRect<'a> {
+    /// Creates an iterator over a rectangle of the stored cells, which must be inside the game,
+    /// which yields their logical state.
+    pub(crate) fn new(cells: &'a [Cell], width: usize, inverted: bool, rect: Rect) -> Self {
+        CellsInRect {
+            cells,
+            width,
+            inverted,
+            rect,
+            front: 0,
+            back: rect.width * rect.height,
+        }
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> ((usize, usize), Cell) {
+        let row = self.rect.y + i / self.rect.width;
+        let col = self.rect.x + i % self.rect.width;
+        let cell = self.cells[row * self.width + col];
+        ((row, col), logical(cell, self.inverted))
+    }
+}
+
+impl Iterator for CellsInRect<'_> {
+    type Item = ((usize, usize), Cell);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.front += 1;
+        Some(self.get(self.front - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for CellsInRect<'_> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+
+        self.back -= 1;
+        Some(self.get(self.back))
+    }
+}
+
+impl ExactSizeIterator for CellsInRect<'_> {}
+
+impl FusedIterator for CellsInRect<'_> {}
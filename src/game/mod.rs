@@ -1,24 +1,60 @@
 //! Contains the [`Game`] struct and its iterators.
 
+mod activity;
+mod diff;
+mod history;
 mod iter;
-mod panic;
+pub(crate) mod panic;
+mod snapshot;
+mod terminal;
+mod text;
+mod undo;
+mod viewport;
 
-use crate::cell::Cell;
+use crate::{cell::Cell, rule::Rule, Rect};
 use alloc::{vec, vec::Vec};
-use core::ops::{Index, IndexMut};
+use core::{
+    iter::FromIterator,
+    ops::{Index, IndexMut},
+};
+pub use diff::Diff;
 pub use iter::*;
 #[cfg(any(test, feature = "use-rand"))]
-use rand::distributions::{Bernoulli, Distribution};
+use rand::{
+    distributions::{Bernoulli, Distribution},
+    Rng,
+};
+pub use snapshot::SnapshotError;
+pub use terminal::{Terminal, TerminalMode, TerminalOptions};
+pub use text::{Glyphs, ParseGameError};
+pub use viewport::{Viewport, Zoom};
 
 /// An instance of Conway's Game of Life.
 ///
+/// It runs [`Rule::LIFE`] by default, but any other life-like [`Rule`] can be used instead.
+///
+/// ## B0 rules
+/// When running a [B0 rule](Rule#b0-rules), the stored cells may be the inverse of the logical
+/// state (see [`is_inverted`](Game::is_inverted)). Every method works with the logical state,
+/// except for [`get_row`](Game::get_row), which returns a slice of the stored cells. Methods which
+/// give mutable access to cells, such as [`IndexMut`] and [`get_row_mut`](Game::get_row_mut),
+/// first flip the stored cells back so that they match the logical state.
+///
 /// TODO: docs
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone)]
 pub struct Game {
     cells: Vec<Cell>,
     next: Vec<Cell>,
     width: usize,
     height: usize,
+    rule: Rule,
+    inverted: bool,
+    /// Whether the previous generation, stored in `next`, is inverted.
+    prev_inverted: bool,
+    generation: u64,
+    history: Option<Vec<u8>>,
+    activity: Option<activity::Activity>,
+    undo: Option<undo::Undo>,
 }
 
 impl Game {
@@ -43,20 +79,313 @@ impl Game {
             height,
             cells,
             next,
+            rule: Rule::LIFE,
+            inverted: false,
+            prev_inverted: false,
+            generation: 0,
+            history: None,
+            activity: None,
+            undo: None,
         }
     }
 
-    /// Ticks once.
+    /// Creates a new instance of a game running a specific rule.
+    ///
+    /// # Panics
+    /// Panics if `width < 1`, `height < 1`, or if `width * height > isize::MAX`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Rule};
+    /// let highlife = "B36/S23".parse().unwrap();
+    /// let game = Game::with_rule(10, 10, highlife);
+    /// assert_eq!(game.rule(), highlife);
+    /// ```
+    pub fn with_rule(width: usize, height: usize, rule: Rule) -> Game {
+        let mut game = Game::new(width, height);
+        game.rule = rule;
+        game
+    }
+
+    /// Gets the rule this game is running.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Rule};
+    /// let game = Game::new(10, 10);
+    /// assert_eq!(game.rule(), Rule::LIFE);
+    /// ```
+    #[inline]
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Changes the rule this game is running. The cells are left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Rule, Cell::*};
+    /// let mut game = Game::new(3, 3);
+    /// game.set_rule("B1/S".parse().unwrap());
+    ///
+    /// game[(1, 1)] = Alive;
+    /// game.tick();
+    /// assert_eq!(game.population(), 8);
+    /// ```
+    #[inline]
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// Returns `true` if the stored cells are currently the inverse of the logical state.
+    ///
+    /// This can only happen while running a [B0 rule](Rule#b0-rules), and only matters for
+    /// [`get_row`](Game::get_row), which returns the stored cells.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::with_rule(10, 10, "B0/S".parse().unwrap());
+    /// assert!(!game.is_inverted());
+    ///
+    /// // every cell is born, but the stored background stays dead
+    /// game.tick();
+    /// assert!(game.is_inverted());
+    /// assert!(game.all_alive());
+    /// assert_eq!(game[(0, 0)], Alive);
+    /// assert!(game.row(0).all(|cell| cell == Alive));
+    ///
+    /// // mutable access flips the stored cells back first
+    /// game[(0, 0)] = Dead;
+    /// assert!(!game.is_inverted());
+    /// assert_eq!(game.population(), 99);
+    /// assert_eq!(game[(0, 0)], Dead);
+    /// assert_eq!(game[(1, 0)], Alive);
+    /// ```
+    #[inline]
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Gets the current generation, which starts at `0`.
+    ///
+    /// Every kind of tick adds one, except for [`untick`](Game::untick), which subtracts one
+    /// without going below `0`.
     ///
     /// # Examples
     /// ```
     /// # use cgol::Game;
-    /// let mut game = Game::new(100, 100);
-    /// game.fill_random(0.5);
+    /// let mut game = Game::new(3, 3);
+    /// game.tick();
+    /// game.tick();
+    /// assert_eq!(game.generation(), 2);
+    /// ```
+    #[inline]
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Sets the current generation, without changing the cells.
+    #[inline]
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+
+    /// Ticks once.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(5, 5);
+    ///
+    /// // a blinker
+    /// game[(1, 2)] = Alive;
+    /// game[(2, 2)] = Alive;
+    /// game[(3, 2)] = Alive;
+    ///
     /// game.tick();
-    /// // view the game somehow...
+    /// assert_eq!(game.col(2).collect::<Vec<_>>(), &[Dead, Alive, Alive, Alive, Dead]);
+    /// assert!(game.row(2).eq([Dead, Dead, Alive, Dead, Dead]));
     /// ```
     pub fn tick(&mut self) {
+        self.before_tick();
+        self.advance();
+        self.after_tick(true);
+    }
+
+    /// Ticks once, without updating anything other than the cells.
+    fn advance(&mut self) {
+        let next_inverted = self.rule.next_inverted(self.inverted);
+        let table = self.rule.table(self.inverted, next_inverted);
+
+        self.step(&table, |_, new| new);
+        self.swap_buffers();
+        self.prev_inverted = self.inverted;
+        self.inverted = next_inverted;
+    }
+
+    /// Updates everything that keeps track of the cells over time, so that any changes made
+    /// since the last tick are included. This must be called once at the start of every kind of
+    /// tick.
+    fn before_tick(&mut self) {
+        self.record_history();
+        self.record_activity(false);
+        self.record_undo();
+    }
+
+    /// Updates everything that keeps track of the cells over time. This must be called once at
+    /// the end of every kind of tick, where `forwards` is `false` if it went back a generation.
+    fn after_tick(&mut self, forwards: bool) {
+        self.generation = if forwards {
+            self.generation.saturating_add(1)
+        } else {
+            self.generation.saturating_sub(1)
+        };
+
+        self.record_history();
+        self.record_activity(true);
+
+        // `untick` records itself, since it overwrites the generation it went back from
+        if forwards {
+            self.record_undo_tick(self.generation);
+        }
+    }
+
+    /// Ticks once as a second-order reversible automaton.
+    ///
+    /// The next generation is the rule's output XORed with the previous generation (the one before
+    /// the last tick). Because of this, it can be undone exactly with [`untick`](Game::untick).
+    ///
+    /// [B0 rules](Rule#b0-rules) are applied directly to the cells, without any emulation, since
+    /// second-order automata don't keep a stable background anyways.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(3, 3);
+    /// game[(1, 1)] = Alive;
+    ///
+    /// // a lone cell dies, and the previous generation was empty
+    /// game.tick_reversible();
+    /// assert!(game.all_dead());
+    ///
+    /// // now the previous generation is the lone cell
+    /// game.tick_reversible();
+    /// assert_eq!(game[(1, 1)], Alive);
+    /// assert_eq!(game.population(), 1);
+    /// ```
+    pub fn tick_reversible(&mut self) {
+        self.before_tick();
+        let table = self.uninverted_table();
+
+        self.step(&table, |prev, new| Cell::from(prev != new));
+        self.swap_buffers();
+        self.after_tick(true);
+    }
+
+    /// Runs [`tick_reversible`](Game::tick_reversible) backwards once. At generation `0`, there
+    /// is nothing to go back to, so this does nothing.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(16, 16);
+    ///
+    /// // an r-pentomino
+    /// game[(7, 6)] = Alive;
+    /// game[(8, 6)] = Alive;
+    /// game[(6, 7)] = Alive;
+    /// game[(7, 7)] = Alive;
+    /// game[(7, 8)] = Alive;
+    /// let original = game.clone();
+    ///
+    /// for _ in 0..100 {
+    ///     game.tick_reversible();
+    /// }
+    /// assert_ne!(game, original);
+    ///
+    /// for _ in 0..100 {
+    ///     game.untick();
+    /// }
+    /// assert_eq!(game, original);
+    /// ```
+    ///
+    /// This also works after ticking normally with a [B0 rule](Rule#b0-rules), where the previous
+    /// generation is stored inverted:
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::with_rule(8, 8, "B0/S8".parse().unwrap());
+    /// game[(3, 3)] = Alive;
+    /// game[(4, 3)] = Alive;
+    /// game.tick();
+    /// let before = game.clone();
+    /// game.tick();
+    ///
+    /// for _ in 0..5 {
+    ///     game.tick_reversible();
+    /// }
+    /// for _ in 0..6 {
+    ///     game.untick();
+    /// }
+    /// assert_eq!(game, before);
+    /// ```
+    pub fn untick(&mut self) {
+        if self.generation == 0 {
+            return;
+        }
+
+        self.before_tick();
+        let table = self.uninverted_table();
+
+        // the previous generation becomes the current one, which can then find its own previous
+        // generation, since `current = f(previous) ^ previous previous`
+        self.swap_buffers();
+        self.record_undo_tick(self.generation - 1);
+        self.step(&table, |current, new| Cell::from(current != new));
+        self.after_tick(false);
+    }
+
+    /// Gets the rule's table for running without B0 emulation, un-inverting the cells and the
+    /// previous generation first.
+    fn uninverted_table(&mut self) -> [[Cell; 9]; 2] {
+        self.uninvert();
+
+        // second-order ticks read the previous generation too, so it has to be logical as well
+        if self.prev_inverted {
+            for cell in &mut self.next {
+                *cell = !*cell;
+            }
+            self.prev_inverted = false;
+        }
+        self.rule.table(false, false)
+    }
+
+    /// Flips the stored cells back if they're inverted, so they match the logical state. This must
+    /// be called before giving out mutable access to the cells.
+    fn uninvert(&mut self) {
+        if self.inverted {
+            for cell in &mut self.cells {
+                *cell = !*cell;
+            }
+            self.inverted = false;
+        }
+    }
+
+    /// Returns `true` if the cell at an index is logically alive, taking B0 emulation into account.
+    #[inline]
+    fn is_alive(&self, index: usize) -> bool {
+        (self.cells[index] == Cell::Alive) ^ self.inverted
+    }
+
+    /// Swaps the current and previous generations.
+    #[inline]
+    fn swap_buffers(&mut self) {
+        core::mem::swap(&mut self.cells, &mut self.next);
+    }
+
+    /// Applies the rule to every cell in `cells`, then writes `combine(next, output)` into `next`.
+    #[inline(always)]
+    fn step(&mut self, table: &[[Cell; 9]; 2], combine: impl Fn(Cell, Cell) -> Cell) {
         /*
         Safety requires the following to be true:
           1. cells.len() == next.len() == width * height
@@ -137,19 +466,22 @@ impl Game {
                     `current_index = row + col` also works, but it's 10% slower for some reason :/
                     so now we need to declare a variable aaaaall the way at the start of the loop
                     */
-                    *self.next.get_unchecked_mut(current_index) = match neighbor_count {
-                        3 => Cell::Alive,
-                        2 => *self.cells.get_unchecked(current_index),
-                        _ => Cell::Dead,
-                    };
+                    let cell = *self.cells.get_unchecked(current_index);
+                    let next = self.next.get_unchecked_mut(current_index);
+
+                    // cells are 0 or 1, and there are at most 8 neighbors
+                    *next = combine(
+                        *next,
+                        *table
+                            .get_unchecked(cell as usize)
+                            .get_unchecked(neighbor_count as usize),
+                    );
                 } // end unsafe block
 
                 current_index += 1;
             } // end col loop
         } // end row loop
-
-        self.cells.copy_from_slice(&self.next);
-    } // end tick()
+    } // end step()
 
     /// Gets this game's width.
     ///
@@ -205,7 +537,7 @@ impl Game {
     /// ```
     pub fn get(&self, row: usize, col: usize) -> Option<Cell> {
         if row < self.height && col < self.width {
-            Some(self.cells[row * self.width + col])
+            Some(self[(col, row)])
         } else {
             None
         }
@@ -213,6 +545,8 @@ impl Game {
 
     /// Mutably gets a specific cell, returning `None` if out of bounds.
     ///
+    /// If this game is [inverted](Game::is_inverted), the stored cells are flipped back first.
+    ///
     /// # Example
     /// ```
     /// # use cgol::{Game, Cell::*};
@@ -222,11 +556,11 @@ impl Game {
     ///     *cell = Alive;
     /// }
     ///
-    /// assert_eq!(game.get_row(0), &[Alive, Dead, Dead]);
+    /// assert!(game.row(0).eq([Alive, Dead, Dead]));
     /// ```
     pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut Cell> {
         if row < self.height && col < self.width {
-            Some(&mut self.cells[row * self.width + col])
+            Some(&mut self[(col, row)])
         } else {
             None
         }
@@ -234,18 +568,24 @@ impl Game {
 
     /// Gets a slice of a specific row.
     ///
-    /// Due to how cells are stored, this isn't possible for columns.
+    /// Due to how cells are stored, this isn't possible for columns. Unlike every other method,
+    /// this returns the stored cells, which are the inverse of the logical state if this game is
+    /// [inverted](Game::is_inverted), so [`row`](Game::row) should be used instead.
     ///
     /// # Panics
     /// Panics if `row` is out of bounds.
-    ///
-    /// # Examples
-    /// ```
-    /// # use cgol::{Game, Cell::*};
-    /// let mut game = Game::new(3, 5);
-    /// assert_eq!(game.get_row(0), &[Dead, Dead, Dead]);
-    /// ```
+    #[deprecated(
+        note = "returns the stored cells, which are inverted for some B0 games, use `row`"
+    )]
     pub fn get_row(&self, row: usize) -> &[Cell] {
+        self.stored_row(row)
+    }
+
+    /// Gets the stored cells of a specific row, which are inverted if this game is.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub(crate) fn stored_row(&self, row: usize) -> &[Cell] {
         if row >= self.height {
             panic::height(row, self.height);
         }
@@ -258,7 +598,8 @@ impl Game {
 
     /// Gets a mutable slice of a specific row.
     ///
-    /// Due to how cells are stored, this isn't possible for columns.
+    /// Due to how cells are stored, this isn't possible for columns. If this game is
+    /// [inverted](Game::is_inverted), the stored cells are flipped back first.
     ///
     /// # Panics
     /// Panics if `row` is out of bounds.
@@ -270,15 +611,17 @@ impl Game {
     /// let mut row = game.get_row_mut(0);
     /// row[0] = Alive;
     ///
-    /// assert_eq!(game.get_row(0), &[Alive, Dead, Dead]);
+    /// assert!(game.row(0).eq([Alive, Dead, Dead]));
     /// ```
     pub fn get_row_mut(&mut self, row: usize) -> &mut [Cell] {
         if row >= self.height {
             panic::height(row, self.height);
         }
 
+        self.uninvert();
         let begin = row * self.width;
         let end = begin + self.width;
+        self.record_undo_edit(begin, 1, self.width);
 
         &mut self.cells[begin..end]
     }
@@ -299,12 +642,14 @@ impl Game {
     /// assert_eq!(row.next(), Some(Dead));
     /// assert_eq!(row.next(), None);
     /// ```
-    pub fn row(&self, row: usize) -> Row {
-        self.get_row(row).iter().copied()
+    pub fn row(&self, row: usize) -> Row<'_> {
+        Row::new(self.stored_row(row), self.inverted)
     }
 
     /// Gets a mutable iterator over the cells in a specific row.
     ///
+    /// If this game is [inverted](Game::is_inverted), the stored cells are flipped back first.
+    ///
     /// # Panics
     /// Panics if `row` is out of bounds.
     ///
@@ -317,11 +662,11 @@ impl Game {
     ///     *cell = Alive;
     /// }
     ///
-    /// assert_eq!(game.get_row(0), &[Alive, Alive, Alive]);
-    /// # assert_eq!(game.get_row(1), &[Dead, Dead, Dead]);
+    /// assert!(game.row(0).eq([Alive, Alive, Alive]));
+    /// # assert!(game.row(1).eq([Dead, Dead, Dead]));
     /// ```
-    pub fn row_mut(&mut self, row: usize) -> RowMut {
-        self.get_row_mut(row).iter_mut()
+    pub fn row_mut(&mut self, row: usize) -> RowMut<'_> {
+        RowMut::new(self.get_row_mut(row))
     }
 
     /// Gets an iterator over the cells in a specific column.
@@ -340,16 +685,18 @@ impl Game {
     /// assert_eq!(col.next(), Some(Dead));
     /// assert_eq!(col.next(), None);
     /// ```
-    pub fn col(&self, col: usize) -> Col {
+    pub fn col(&self, col: usize) -> Col<'_> {
         if col >= self.width {
             panic::width(col, self.width);
         }
 
-        self.cells.iter().copied().skip(col).step_by(self.width)
+        Col::new(&self.cells[col..], self.width, self.inverted)
     }
 
     /// Gets a mutable iterator over the cells in a specific column.
     ///
+    /// If this game is [inverted](Game::is_inverted), the stored cells are flipped back first.
+    ///
     /// # Panics
     /// Panics if `col` is out of bounds.
     ///
@@ -367,12 +714,119 @@ impl Game {
     /// # let mut col_2_electric_boogaloo: Vec<_> = game.col(1).collect();
     /// # assert_eq!(&col_2_electric_boogaloo, &[Dead, Dead, Dead]);
     /// ```
-    pub fn col_mut(&mut self, col: usize) -> ColMut {
+    pub fn col_mut(&mut self, col: usize) -> ColMut<'_> {
         if col >= self.width {
             panic::width(col, self.width);
         }
 
-        self.cells.iter_mut().skip(col).step_by(self.width)
+        self.uninvert();
+        self.record_undo_edit(col, self.width, self.height);
+        ColMut::new(&mut self.cells[col..], self.width)
+    }
+
+    /// Gets an iterator over every row, from top to bottom.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(2, 3);
+    /// game[(1, 2)] = Alive;
+    ///
+    /// let last = game.rows().next_back().unwrap();
+    /// assert_eq!(last.collect::<Vec<_>>(), [Dead, Alive]);
+    /// assert_eq!(game.rows().len(), 3);
+    /// ```
+    pub fn rows(&self) -> Rows<'_> {
+        Rows::new(&self.cells, self.width, self.inverted)
+    }
+
+    /// Gets an iterator over every column, from left to right.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(2, 3);
+    /// game[(1, 2)] = Alive;
+    ///
+    /// let last = game.cols().next_back().unwrap();
+    /// assert_eq!(last.rev().collect::<Vec<_>>(), [Alive, Dead, Dead]);
+    /// assert_eq!(game.cols().len(), 2);
+    /// ```
+    pub fn cols(&self) -> Cols<'_> {
+        Cols::new(&self.cells, self.width, self.inverted)
+    }
+
+    /// Gets an iterator over the positions of alive cells, as `(row, col)`, in row-major order.
+    ///
+    /// This follows the logical state, so it works the same on [inverted](Game::is_inverted)
+    /// games.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(100, 100);
+    /// game[(50, 2)] = Alive;
+    /// game[(3, 70)] = Alive;
+    ///
+    /// let alive: Vec<_> = game.alive_cells().collect();
+    /// assert_eq!(alive, [(2, 50), (70, 3)]);
+    /// ```
+    pub fn alive_cells(&self) -> AliveCells<'_> {
+        AliveCells::new(&self.cells, self.width, self.inverted)
+    }
+
+    /// Gets an iterator over every cell along with its position, as `((row, col), cell)`, in
+    /// row-major order.
+    ///
+    /// Like [`alive_cells`](Game::alive_cells), this yields the logical state of each cell, even
+    /// if this game is [inverted](Game::is_inverted).
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(2, 2);
+    /// game[(1, 0)] = Alive;
+    ///
+    /// let mut cells = game.cells_with_coords();
+    /// assert_eq!(cells.next(), Some(((0, 0), Dead)));
+    /// assert_eq!(cells.next(), Some(((0, 1), Alive)));
+    /// assert_eq!(cells.len(), 2);
+    /// ```
+    pub fn cells_with_coords(&self) -> CellsWithCoords<'_> {
+        CellsWithCoords::new(&self.cells, self.width, self.inverted)
+    }
+
+    /// Gets an iterator over the cells in a rectangle along with their positions, as
+    /// `((row, col), cell)`, in row-major order. Parts of the rectangle outside of this game are
+    /// left out.
+    ///
+    /// Like [`alive_cells`](Game::alive_cells), this yields the logical state of each cell, even
+    /// if this game is [inverted](Game::is_inverted).
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Rect, Cell::*};
+    /// let mut game = Game::new(10, 10);
+    /// game[(8, 9)] = Alive;
+    ///
+    /// let cells: Vec<_> = game.cells_in_rect(Rect::new(8, 8, 5, 5)).collect();
+    /// assert_eq!(
+    ///     cells,
+    ///     [
+    ///         ((8, 8), Dead),
+    ///         ((8, 9), Dead),
+    ///         ((9, 8), Alive),
+    ///         ((9, 9), Dead),
+    ///     ]
+    /// );
+    /// ```
+    pub fn cells_in_rect(&self, rect: Rect) -> CellsInRect<'_> {
+        CellsInRect::new(
+            &self.cells,
+            self.width,
+            self.inverted,
+            rect.clamp(self.width, self.height),
+        )
     }
 
     /// Kills all cells.
@@ -386,7 +840,29 @@ impl Game {
     /// assert_eq!(game[(0, 0)], Dead);
     /// ```
     pub fn clear(&mut self) {
+        self.record_undo_board(|game, i| (game.cells[i] == Cell::Alive) != game.inverted);
         self.cells.fill(Cell::Dead);
+        self.inverted = false;
+    }
+
+    /// Counts the alive cells.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(10, 10);
+    /// game[(0, 0)] = Alive;
+    /// game[(5, 5)] = Alive;
+    /// assert_eq!(game.population(), 2);
+    /// ```
+    pub fn population(&self) -> usize {
+        let stored = self.cells.iter().filter(|&&c| c == Cell::Alive).count();
+
+        if self.inverted {
+            self.cells.len() - stored
+        } else {
+            stored
+        }
     }
 
     /// Returns `true` if all cells are [`Dead`](Cell::Dead).
@@ -405,7 +881,15 @@ impl Game {
             c == Cell::Dead
         }
 
-        self.cells.iter().all(is_dead)
+        fn is_alive(&c: &Cell) -> bool {
+            c == Cell::Alive
+        }
+
+        if self.inverted {
+            self.cells.iter().all(is_alive)
+        } else {
+            self.cells.iter().all(is_dead)
+        }
     }
 
     /// Returns `true` if all cells are [`Alive`](Cell::Alive).
@@ -420,11 +904,19 @@ impl Game {
     /// assert!(game.all_alive());
     /// ```
     pub fn all_alive(&self) -> bool {
+        fn is_dead(&c: &Cell) -> bool {
+            c == Cell::Dead
+        }
+
         fn is_alive(&c: &Cell) -> bool {
             c == Cell::Alive
         }
 
-        self.cells.iter().all(is_alive)
+        if self.inverted {
+            self.cells.iter().all(is_dead)
+        } else {
+            self.cells.iter().all(is_alive)
+        }
     }
 
     /// Inverts all cells.
@@ -437,6 +929,7 @@ impl Game {
     /// assert!(game.all_alive());
     /// ```
     pub fn invert(&mut self) {
+        self.record_undo_board(|_, _| true);
         for cell in &mut self.cells {
             *cell = !*cell;
         }
@@ -463,16 +956,180 @@ impl Game {
     /// ```
     #[cfg(any(test, feature = "use-rand"))]
     pub fn fill_random(&mut self, chance: f64) {
-        let mut rng = Bernoulli::new(chance)
+        let cells: Vec<Cell> = Bernoulli::new(chance)
             .unwrap()
-            .sample_iter(rand::thread_rng());
+            .sample_iter(rand::thread_rng())
+            .take(self.cells.len())
+            .map(|val| Cell::from(val as u8))
+            .collect();
 
+        self.record_undo_board(|game, i| (game.cells[i] != cells[i]) != game.inverted);
+        self.cells = cells;
+        self.inverted = false;
+    }
+
+    /// Ticks once, then adds noise: every dead cell is born with a probability of `birth`, and
+    /// every alive cell dies with a probability of `death`.
+    ///
+    /// Using the same probability for both is like running the game at a temperature.
+    ///
+    /// # Panics
+    /// Panics if `birth` or `death` are not in the range `[0, 1]`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::Game;
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let mut game = Game::new(10, 10);
+    ///
+    /// game.tick_noisy(&mut rng, 0.0, 0.0);
+    /// assert!(game.all_dead());
+    ///
+    /// game.tick_noisy(&mut rng, 1.0, 0.0);
+    /// assert!(game.all_alive());
+    ///
+    /// // the same seed always gives the same result
+    /// let mut a = Game::new(10, 10);
+    /// let mut b = Game::new(10, 10);
+    /// a.tick_noisy(&mut StdRng::seed_from_u64(1), 0.1, 0.1);
+    /// b.tick_noisy(&mut StdRng::seed_from_u64(1), 0.1, 0.1);
+    /// assert_eq!(a, b);
+    /// ```
+    #[cfg(any(test, feature = "use-rand"))]
+    pub fn tick_noisy<R: Rng + ?Sized>(&mut self, rng: &mut R, birth: f64, death: f64) {
+        self.before_tick();
+        let birth = Bernoulli::new(birth).unwrap();
+        let death = Bernoulli::new(death).unwrap();
+
+        self.advance();
+
+        let inverted = self.inverted;
         for cell in &mut self.cells {
-            *cell = match rng.next() {
-                Some(val) => Cell::from(val as u8),
-                None => unreachable!(),
+            let alive = (*cell == Cell::Alive) ^ inverted;
+            let flip = if alive {
+                death.sample(rng)
+            } else {
+                birth.sample(rng)
             };
+
+            if flip {
+                *cell = !*cell;
+            }
+        }
+
+        self.after_tick(true);
+    }
+
+    /// Ticks once asynchronously, by updating randomly chosen cells one at a time.
+    ///
+    /// As many cells are updated as there are cells in the game, so each cell is updated once on
+    /// average. Each update sees the changes made by the ones before it.
+    ///
+    /// [B0 rules](Rule#b0-rules) are applied directly to the cells, without any emulation, since
+    /// there is no single generation for the background to alternate on.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let mut game = Game::new(10, 10);
+    /// game[(5, 5)] = Alive;
+    ///
+    /// // a lone cell can only die
+    /// game.tick_random_sequential(&mut StdRng::seed_from_u64(42));
+    /// assert!(game.population() <= 1);
+    /// ```
+    #[cfg(any(test, feature = "use-rand"))]
+    pub fn tick_random_sequential<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.before_tick();
+        let table = self.uninverted_table();
+        let area = self.cells.len();
+
+        // keep the previous generation around, like the other ticks do
+        self.next.copy_from_slice(&self.cells);
+
+        for _ in 0..area {
+            let index = rng.gen_range(0..area);
+            let neighbors = self.neighbor_count(index);
+            let cell = &mut self.cells[index];
+            *cell = table[*cell as usize][neighbors as usize];
+        }
+
+        self.after_tick(true);
+    }
+
+    /// Ticks once α-asynchronously: every cell is updated with a probability of `alpha`, and
+    /// otherwise stays the same. Updated cells all see the same previous generation.
+    ///
+    /// [B0 rules](Rule#b0-rules) are applied directly to the cells, without any emulation, since
+    /// there is no single generation for the background to alternate on.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is not in the range `[0, 1]`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// use rand::{rngs::StdRng, SeedableRng};
+    ///
+    /// let mut rng = StdRng::seed_from_u64(42);
+    /// let mut game = Game::new(5, 5);
+    /// game[(1, 2)] = Alive;
+    /// game[(2, 2)] = Alive;
+    /// game[(3, 2)] = Alive;
+    /// let mut sync = game.clone();
+    ///
+    /// game.tick_alpha_async(&mut rng, 0.0);
+    /// assert!(game.row(2).eq([Dead, Alive, Alive, Alive, Dead]));
+    ///
+    /// // updating every cell is the same as a normal tick
+    /// game.tick_alpha_async(&mut rng, 1.0);
+    /// sync.tick();
+    /// assert!(game.row(2).eq(sync.row(2)));
+    /// ```
+    #[cfg(any(test, feature = "use-rand"))]
+    pub fn tick_alpha_async<R: Rng + ?Sized>(&mut self, rng: &mut R, alpha: f64) {
+        self.before_tick();
+        let alpha = Bernoulli::new(alpha).unwrap();
+        let table = self.uninverted_table();
+
+        self.step(&table, |_, new| new);
+        for (next, &cell) in self.next.iter_mut().zip(&self.cells) {
+            if !alpha.sample(rng) {
+                *next = cell;
+            }
         }
+
+        self.swap_buffers();
+        self.after_tick(true);
+    }
+
+    /// Counts the alive neighbors of a single cell.
+    #[cfg(any(test, feature = "use-rand"))]
+    fn neighbor_count(&self, index: usize) -> u8 {
+        let (row, col) = (index / self.width, index % self.width);
+        let up = if row == 0 { self.height - 1 } else { row - 1 };
+        let down = if row == self.height - 1 { 0 } else { row + 1 };
+        let left = if col == 0 { self.width - 1 } else { col - 1 };
+        let right = if col == self.width - 1 { 0 } else { col + 1 };
+
+        [
+            (up, left),
+            (up, col),
+            (up, right),
+            (row, right),
+            (down, right),
+            (down, col),
+            (down, left),
+            (row, left),
+        ]
+        .iter()
+        .fold(0, |n, &(row, col)| {
+            n + self.cells[row * self.width + col] as u8
+        })
     }
 }
 
@@ -486,10 +1143,16 @@ impl Index<(usize, usize)> for Game {
             panic::width(col, self.width);
         }
 
-        &self.cells[row * self.width + col]
+        let cell = &self.cells[row * self.width + col];
+        match (self.inverted, cell) {
+            (false, _) => cell,
+            (true, Cell::Alive) => &Cell::Dead,
+            (true, Cell::Dead) => &Cell::Alive,
+        }
     }
 }
 
+/// If the game is [inverted](Game::is_inverted), the stored cells are flipped back first.
 impl IndexMut<(usize, usize)> for Game {
     fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut Cell {
         if row >= self.height {
@@ -498,7 +1161,73 @@ impl IndexMut<(usize, usize)> for Game {
             panic::width(col, self.width);
         }
 
-        &mut self.cells[row * self.width + col]
+        self.uninvert();
+        let index = row * self.width + col;
+        self.record_undo_edit(index, 1, 1);
+        &mut self.cells[index]
+    }
+}
+
+/// Two games are equal if they have the same size, rule and logical cells. Everything else, like
+/// the generation, the previous generation and any recorded history, is ignored.
+///
+/// # Examples
+/// ```
+/// # use cgol::{Game, Cell::*};
+/// let mut blinker = Game::new(5, 5);
+/// blinker[(1, 2)] = Alive;
+/// blinker[(2, 2)] = Alive;
+/// blinker[(3, 2)] = Alive;
+///
+/// let mut game = blinker.clone();
+/// game.tick();
+/// assert_ne!(game, blinker);
+/// game.tick();
+/// assert_eq!(game, blinker);
+/// ```
+impl PartialEq for Game {
+    fn eq(&self, other: &Game) -> bool {
+        if self.width != other.width || self.height != other.height || self.rule != other.rule {
+            return false;
+        }
+
+        if self.inverted == other.inverted {
+            self.cells == other.cells
+        } else {
+            self.cells.iter().zip(&other.cells).all(|(a, b)| a != b)
+        }
+    }
+}
+
+impl Eq for Game {}
+
+/// Creates a game from the positions of alive cells, as `(row, col)`. The game is just big enough
+/// to fit every cell, or 1x1 if there are none.
+///
+/// # Panics
+/// Panics if a game big enough to fit every cell can't be created, for the same reasons as
+/// [`Game::new`], such as a position of `usize::MAX`.
+///
+/// # Examples
+/// ```
+/// # use cgol::{Game, Cell::*};
+/// let game: Game = vec![(0, 1), (2, 3)].into_iter().collect();
+///
+/// assert_eq!((game.width(), game.height()), (4, 3));
+/// assert_eq!(game.alive_cells().collect::<Vec<_>>(), [(0, 1), (2, 3)]);
+/// ```
+impl FromIterator<(usize, usize)> for Game {
+    fn from_iter<I: IntoIterator<Item = (usize, usize)>>(iter: I) -> Game {
+        let alive: Vec<(usize, usize)> = iter.into_iter().collect();
+        let size = |i: usize| i.checked_add(1).expect("width * height overflow");
+        let width = alive.iter().map(|&(_, col)| size(col)).max().unwrap_or(1);
+        let height = alive.iter().map(|&(row, _)| size(row)).max().unwrap_or(1);
+
+        let mut game = Game::new(width, height);
+        for (row, col) in alive {
+            game.cells[row * width + col] = Cell::Alive;
+        }
+        game
     }
 }
 
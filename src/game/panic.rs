@@ -1,40 +1,8 @@
-//! Contains panics used by [`Game`]. They are here to reduce code size as much as possible.
+//! Contains panics used by [`Game`] and the other automata. They are here to reduce code size as
+//! much as possible.
 
 use super::Game;
-
-#[cold]
-#[inline(never)]
-#[track_caller]
-pub(super) fn width_is_zero() {
-    panic!("width must not be 0");
-}
-
-#[cold]
-#[inline(never)]
-#[track_caller]
-pub(super) fn height_is_zero() {
-    panic!("height must not be 0");
-}
-
-#[cold]
-#[inline(never)]
-#[track_caller]
-pub(super) fn width(val: usize, width: usize) {
-    panic!(
-        "index out of bounds: {} is {} but the index is {}",
-        "width", width, val
-    );
-}
-
-#[cold]
-#[inline(never)]
-#[track_caller]
-pub(super) fn height(val: usize, height: usize) {
-    panic!(
-        "index out of bounds: {} is {} but the index is {}",
-        "height", height, val
-    );
-}
+pub(crate) use crate::panic::{height, height_is_zero, width, width_is_zero};
 
 #[cold]
 #[inline(never)]
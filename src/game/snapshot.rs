@@ -0,0 +1,379 @@
+//! Contains [`Game`]'s binary snapshot format, for saving a game and resuming it later.
+
+use super::Game;
+use crate::{cell::Cell, rule::Rule};
+use alloc::vec::Vec;
+use core::{convert::TryFrom, fmt};
+
+const MAGIC: &[u8; 4] = b"CGOL";
+const VERSION: u16 = 1;
+/// The length of everything before the cells.
+const HEADER_LEN: usize = 36;
+const CHECKSUM_LEN: usize = 4;
+const FLAG_INVERTED: u16 = 1 << 0;
+
+/// An error which can be returned when loading a snapshot.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// The data doesn't start with the magic bytes, so it isn't a snapshot.
+    NotASnapshot,
+    /// The snapshot was written in a version of the format which isn't supported.
+    UnsupportedVersion(u16),
+    /// The data ended before the snapshot did.
+    Truncated,
+    /// There is more data after the end of the snapshot.
+    TrailingData,
+    /// The checksum doesn't match the data, so it was corrupted.
+    ChecksumMismatch {
+        /// The checksum stored in the snapshot.
+        expected: u32,
+        /// The checksum of the data which was read.
+        found: u32,
+    },
+    /// The width or height is `0`, or the game is too big for this platform.
+    InvalidSize {
+        /// The stored width.
+        width: u64,
+        /// The stored height.
+        height: u64,
+    },
+    /// The rule has neighbor counts greater than `8`, or unknown flags are set.
+    InvalidHeader,
+    /// Reading or writing failed.
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::NotASnapshot => f.write_str("not a snapshot"),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::Truncated => f.write_str("snapshot is truncated"),
+            SnapshotError::TrailingData => f.write_str("unexpected data after snapshot"),
+            SnapshotError::ChecksumMismatch { expected, found } => write!(
+                f,
+                "snapshot is corrupted, expected checksum {:08x} but found {:08x}",
+                expected, found
+            ),
+            SnapshotError::InvalidSize { width, height } => {
+                write!(f, "invalid snapshot size {}x{}", width, height)
+            }
+            SnapshotError::InvalidHeader => f.write_str("invalid snapshot header"),
+            #[cfg(feature = "std")]
+            SnapshotError::Io(err) => write!(f, "snapshot i/o failed: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for SnapshotError {
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::UnexpectedEof => SnapshotError::Truncated,
+            _ => SnapshotError::Io(err),
+        }
+    }
+}
+
+/// Computes the CRC-32 (as used by zlib and PNG) of some bytes, which are split into parts.
+fn crc32(parts: &[&[u8]]) -> u32 {
+    !parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .fold(!0, |crc, &byte| {
+            (0..8).fold(crc ^ byte as u32, |crc, _| {
+                (crc >> 1) ^ (0xEDB8_8320 & (crc & 1).wrapping_neg())
+            })
+        })
+}
+
+/// Reads a little endian number from the header. The caller makes sure it's long enough.
+fn read_u16(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([bytes[at], bytes[at + 1]])
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    let mut buf = [0; 8];
+    buf.copy_from_slice(&bytes[at..at + 8]);
+    u64::from_le_bytes(buf)
+}
+
+/// The parts of a header which are needed to read the rest of a snapshot.
+struct Header {
+    width: usize,
+    height: usize,
+    inverted: bool,
+    generation: u64,
+    rule: Rule,
+}
+
+impl Header {
+    /// Checks and parses a header.
+    fn parse(bytes: &[u8; HEADER_LEN]) -> Result<Header, SnapshotError> {
+        if &bytes[..4] != MAGIC {
+            return Err(SnapshotError::NotASnapshot);
+        }
+
+        let version = read_u16(bytes, 4);
+        if version != VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let flags = read_u16(bytes, 6);
+        let rule = Rule::from_masks(read_u16(bytes, 32), read_u16(bytes, 34));
+        let rule = match rule {
+            Some(rule) if flags & !FLAG_INVERTED == 0 => rule,
+            _ => return Err(SnapshotError::InvalidHeader),
+        };
+
+        let (width, height) = (read_u64(bytes, 8), read_u64(bytes, 16));
+        let invalid_size = SnapshotError::InvalidSize { width, height };
+        let (width, height) = match (usize::try_from(width), usize::try_from(height)) {
+            (Ok(width), Ok(height)) if width != 0 && height != 0 => (width, height),
+            _ => return Err(invalid_size),
+        };
+
+        match width.checked_mul(height) {
+            Some(area) if area <= isize::MAX as usize => {}
+            _ => return Err(invalid_size),
+        }
+
+        Ok(Header {
+            width,
+            height,
+            inverted: flags & FLAG_INVERTED != 0,
+            generation: read_u64(bytes, 24),
+            rule,
+        })
+    }
+
+    /// The number of bytes the cells take up.
+    fn cells_len(&self) -> usize {
+        (self.width * self.height - 1) / 8 + 1
+    }
+
+    /// Checks the checksum, then builds the game.
+    ///
+    /// `header` and `cells` must together be all of the data before the checksum.
+    fn finish(
+        self,
+        header: &[u8],
+        cells: &[u8],
+        checksum: [u8; CHECKSUM_LEN],
+    ) -> Result<Game, SnapshotError> {
+        let expected = u32::from_le_bytes(checksum);
+        let found = crc32(&[header, cells]);
+
+        if expected != found {
+            return Err(SnapshotError::ChecksumMismatch { expected, found });
+        }
+
+        let mut game = Game::with_rule(self.width, self.height, self.rule);
+        game.unpack_cells(cells, self.inverted);
+        game.generation = self.generation;
+
+        Ok(game)
+    }
+}
+
+impl Game {
+    /// Writes a snapshot of this game into bytes.
+    ///
+    /// A snapshot contains the cells, the rule and the [generation](Game::generation), so the
+    /// game can be resumed exactly with [`from_snapshot`](Game::from_snapshot). The history,
+    /// activity and undo layers, and the previous generation used by
+    /// [`tick_reversible`](Game::tick_reversible), aren't saved.
+    ///
+    /// # Format
+    /// A snapshot is laid out as follows, with every number in little endian:
+    ///
+    /// | Bytes | Contents |
+    /// |-------|----------|
+    /// | 4 | The magic bytes `CGOL` |
+    /// | 2 | The format version, currently `1` |
+    /// | 2 | Flags, where bit `0` is set if the stored cells are inverted |
+    /// | 8 | Width |
+    /// | 8 | Height |
+    /// | 8 | Generation |
+    /// | 2 | Birth bitmask, where bit `n` is set if `n` neighbors cause a birth |
+    /// | 2 | Survival bitmask, in the same way |
+    /// | `ceil(width * height / 8)` | The stored cells in row-major order, 8 per byte starting from the lowest bit |
+    /// | 4 | A CRC-32 checksum of everything before it |
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::with_rule(8, 8, "B36/S23".parse().unwrap());
+    /// game[(3, 4)] = Alive;
+    /// game.tick();
+    ///
+    /// let bytes = game.to_snapshot();
+    /// let loaded = Game::from_snapshot(&bytes).unwrap();
+    /// assert_eq!(loaded.rule(), game.rule());
+    /// assert_eq!(loaded.generation(), 1);
+    /// assert!(loaded.row(4).eq(game.row(4)));
+    /// ```
+    pub fn to_snapshot(&self) -> Vec<u8> {
+        let area = self.cells.len();
+        let mut bytes = Vec::with_capacity(HEADER_LEN + (area - 1) / 8 + 1 + CHECKSUM_LEN);
+        let (birth, survival) = self.rule.masks();
+        let flags = if self.inverted { FLAG_INVERTED } else { 0 };
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+        bytes.extend_from_slice(&flags.to_le_bytes());
+        bytes.extend_from_slice(&(self.width as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.height as u64).to_le_bytes());
+        bytes.extend_from_slice(&self.generation.to_le_bytes());
+        bytes.extend_from_slice(&birth.to_le_bytes());
+        bytes.extend_from_slice(&survival.to_le_bytes());
+
+        bytes.extend(self.packed_cells());
+
+        let checksum = crc32(&[&bytes]);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Loads a game from a snapshot written by [`to_snapshot`](Game::to_snapshot).
+    ///
+    /// # Errors
+    /// Returns an error if the snapshot is corrupted, truncated, followed by more data, or was
+    /// written in an unsupported version of the format.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, game::SnapshotError};
+    /// let mut bytes = Game::new(8, 8).to_snapshot();
+    /// bytes[40] ^= 1;
+    ///
+    /// assert!(matches!(
+    ///     Game::from_snapshot(&bytes),
+    ///     Err(SnapshotError::ChecksumMismatch { .. })
+    /// ));
+    /// ```
+    pub fn from_snapshot(bytes: &[u8]) -> Result<Game, SnapshotError> {
+        if bytes.len() < HEADER_LEN {
+            // a wrong file is more likely than a truncated one
+            return Err(match bytes.get(..4) {
+                Some(magic) if magic != MAGIC => SnapshotError::NotASnapshot,
+                _ => SnapshotError::Truncated,
+            });
+        }
+
+        let (header, rest) = bytes.split_at(HEADER_LEN);
+        let mut header_bytes = [0; HEADER_LEN];
+        header_bytes.copy_from_slice(header);
+        let parsed = Header::parse(&header_bytes)?;
+
+        let cells_len = parsed.cells_len();
+        let len = cells_len + CHECKSUM_LEN;
+        if rest.len() < len {
+            return Err(SnapshotError::Truncated);
+        } else if rest.len() > len {
+            return Err(SnapshotError::TrailingData);
+        }
+
+        let (cells, checksum) = rest.split_at(cells_len);
+        let mut checksum_bytes = [0; CHECKSUM_LEN];
+        checksum_bytes.copy_from_slice(checksum);
+        parsed.finish(header, cells, checksum_bytes)
+    }
+
+    /// Writes a snapshot of this game, as described in [`to_snapshot`](Game::to_snapshot).
+    ///
+    /// # Errors
+    /// Returns an error if writing fails.
+    #[cfg(feature = "std")]
+    pub fn save_to<W: std::io::Write>(&self, mut writer: W) -> Result<(), SnapshotError> {
+        writer.write_all(&self.to_snapshot())?;
+        Ok(())
+    }
+
+    /// Reads a game from a snapshot written by [`save_to`](Game::save_to) or
+    /// [`to_snapshot`](Game::to_snapshot).
+    ///
+    /// Only the snapshot is read, so other data can come after it.
+    ///
+    /// # Errors
+    /// Returns an error if reading fails, or for the same reasons as
+    /// [`from_snapshot`](Game::from_snapshot).
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(10, 10);
+    /// game[(2, 7)] = Alive;
+    ///
+    /// let mut file = Vec::new();
+    /// game.save_to(&mut file).unwrap();
+    /// let loaded = Game::load_from(file.as_slice()).unwrap();
+    /// assert_eq!(loaded[(2, 7)], Alive);
+    /// ```
+    ///
+    /// A header claiming a huge game isn't trusted until the cells have actually been read:
+    /// ```
+    /// # use cgol::{Game, game::SnapshotError};
+    /// let mut file = Game::new(10, 10).to_snapshot();
+    ///
+    /// // claim to be 2^20 by 2^20
+    /// file[8..16].copy_from_slice(&(1u64 << 20).to_le_bytes());
+    /// file[16..24].copy_from_slice(&(1u64 << 20).to_le_bytes());
+    /// assert!(matches!(Game::load_from(file.as_slice()), Err(SnapshotError::Truncated)));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn load_from<R: std::io::Read>(mut reader: R) -> Result<Game, SnapshotError> {
+        use std::io::Read;
+
+        let mut header = [0; HEADER_LEN];
+        reader.read_exact(&mut header)?;
+        let parsed = Header::parse(&header)?;
+
+        // the header can't be trusted yet, so only allocate as much as is actually read
+        let len = parsed.cells_len();
+        let mut cells = Vec::new();
+        reader.by_ref().take(len as u64).read_to_end(&mut cells)?;
+        if cells.len() != len {
+            return Err(SnapshotError::Truncated);
+        }
+
+        let mut checksum = [0; CHECKSUM_LEN];
+        reader.read_exact(&mut checksum)?;
+
+        parsed.finish(&header, &cells, checksum)
+    }
+
+    /// Packs the stored cells in row-major order, 8 per byte starting from the lowest bit.
+    pub(crate) fn packed_cells(&self) -> impl Iterator<Item = u8> + '_ {
+        self.cells.chunks(8).map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .fold(0, |byte, (i, &cell)| byte | (cell as u8) << i)
+        })
+    }
+
+    /// Replaces the stored cells with ones packed by [`packed_cells`](Game::packed_cells).
+    ///
+    /// `bytes` must be exactly as long as the packed cells.
+    pub(crate) fn unpack_cells(&mut self, bytes: &[u8], inverted: bool) {
+        for (i, cell) in self.cells.iter_mut().enumerate() {
+            *cell = Cell::from(bytes[i / 8] >> (i % 8) & 1 != 0);
+        }
+        self.inverted = inverted;
+    }
+}
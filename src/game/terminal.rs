@@ -0,0 +1,172 @@
+//! Contains [`Terminal`], which displays a [`Game`] compactly using Unicode block or braille
+//! characters.
+
+use super::{Game, Zoom};
+use crate::{image::Rgb, Rect};
+use core::fmt::{self, Write};
+
+/// How many cells each character covers in a [`Terminal`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalMode {
+    /// Each character is 1 cell wide and 2 cells tall, using `▀`, `▄` and `█`.
+    HalfBlock,
+    /// Each character is 2 cells wide and 4 cells tall, using braille patterns like `⣿`.
+    Braille,
+}
+
+impl Default for TerminalMode {
+    #[inline]
+    fn default() -> Self {
+        TerminalMode::HalfBlock
+    }
+}
+
+/// How to display a [`Game`] in a terminal.
+///
+/// # Examples
+/// ```
+/// # use cgol::{Rect, game::{TerminalMode, TerminalOptions}};
+/// // a 400x200 board fits in 200x50 characters
+/// let options = TerminalOptions {
+///     mode: TerminalMode::Braille,
+///     color: Some(([255, 255, 255], [0, 0, 0])),
+///     viewport: Some(Rect::new(0, 0, 400, 200)),
+///     zoom_out: 0,
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TerminalOptions {
+    /// How many cells each character covers. Defaults to [`TerminalMode::HalfBlock`].
+    pub mode: TerminalMode,
+    /// The colors of alive and dead cells, which are written as 24-bit ANSI escape codes, or
+    /// `None` for plain text. Defaults to `None`.
+    pub color: Option<(Rgb, Rgb)>,
+    /// The cells to display, or `None` to display all of them. Parts outside of the game are left
+    /// out. Defaults to `None`.
+    pub viewport: Option<Rect>,
+    /// How far to zoom out, where each dot or half block covers `2^zoom_out` by `2^zoom_out`
+    /// cells, and is shown if any of them are alive. Defaults to `0`.
+    pub zoom_out: u32,
+}
+
+/// Displays a [`Game`] packing several cells into each character, for terminals.
+///
+/// This is created by the [`Game::terminal`] method.
+#[derive(Debug, Clone, Copy)]
+pub struct Terminal<'a> {
+    game: &'a Game,
+    options: TerminalOptions,
+}
+
+impl fmt::Display for Terminal<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = self.game;
+        let all = Rect::new(0, 0, game.width, game.height);
+        let view = game.viewport(
+            self.options.viewport.unwrap_or(all),
+            Zoom::Out(self.options.zoom_out),
+        );
+        let (width, height) = (view.width(), view.height());
+        let pixels = view.any_alive();
+
+        // whether a dot is shown, which is `false` past the edges
+        let alive =
+            |row: usize, col: usize| row < height && col < width && pixels[row * width + col];
+
+        let (cols, rows) = match self.options.mode {
+            TerminalMode::HalfBlock => (1, 2),
+            TerminalMode::Braille => (2, 4),
+        };
+
+        for top in (0..height).step_by(rows) {
+            if top != 0 {
+                f.write_str("\n")?;
+            }
+
+            if let Some(([ar, ag, ab], [dr, dg, db])) = self.options.color {
+                write!(
+                    f,
+                    "\x1b[38;2;{};{};{};48;2;{};{};{}m",
+                    ar, ag, ab, dr, dg, db
+                )?;
+            }
+
+            for left in (0..width).step_by(cols) {
+                let c = match self.options.mode {
+                    TerminalMode::HalfBlock => match (alive(top, left), alive(top + 1, left)) {
+                        (true, true) => '█',
+                        (true, false) => '▀',
+                        (false, true) => '▄',
+                        (false, false) => ' ',
+                    },
+                    TerminalMode::Braille => {
+                        // the bit of each dot, by row then column
+                        const DOTS: [[u32; 2]; 4] =
+                            [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+                        let mut bits = 0;
+                        for (row, dots) in DOTS.iter().enumerate() {
+                            for (col, &dot) in dots.iter().enumerate() {
+                                if alive(top + row, left + col) {
+                                    bits |= dot;
+                                }
+                            }
+                        }
+                        char::from_u32(0x2800 + bits).unwrap()
+                    }
+                };
+                f.write_char(c)?;
+            }
+
+            if self.options.color.is_some() {
+                f.write_str("\x1b[0m")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Game {
+    /// Returns a wrapper which displays this game with several cells in each character, so large
+    /// games fit in a terminal. Rows of characters are separated by newlines, and there is no
+    /// newline after the last one.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{life, Rect, game::{TerminalMode, TerminalOptions}};
+    /// let glider = life![
+    ///     ".O..",
+    ///     "..O.",
+    ///     "OOO.",
+    ///     "....",
+    /// ];
+    ///
+    /// let options = TerminalOptions::default();
+    /// assert_eq!(glider.terminal(&options).to_string(), " ▀▄ \n▀▀▀ ");
+    ///
+    /// let options = TerminalOptions {
+    ///     mode: TerminalMode::Braille,
+    ///     ..TerminalOptions::default()
+    /// };
+    /// assert_eq!(glider.terminal(&options).to_string(), "⠬⠆");
+    ///
+    /// let options = TerminalOptions {
+    ///     viewport: Some(Rect::new(1, 0, 2, 2)),
+    ///     ..TerminalOptions::default()
+    /// };
+    /// assert_eq!(glider.terminal(&options).to_string(), "▀▄");
+    ///
+    /// let options = TerminalOptions {
+    ///     zoom_out: 1,
+    ///     ..TerminalOptions::default()
+    /// };
+    /// assert_eq!(glider.terminal(&options).to_string(), "██");
+    /// ```
+    pub fn terminal(&self, options: &TerminalOptions) -> Terminal<'_> {
+        Terminal {
+            game: self,
+            options: *options,
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Contains [`Game`]'s text formats: its own, where each row is a line of `.` (dead) and `O`
+//! (alive), and the RLE and plaintext (`.cells`) formats used by other programs.
+
+use super::Game;
+use crate::{cell::Cell, rle, Rule};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+
+/// Displays a [`Game`] using specific characters for alive and dead cells.
+///
+/// This is created by the [`Game::glyphs`] method.
+#[derive(Debug, Clone, Copy)]
+pub struct Glyphs<'a> {
+    game: &'a Game,
+    alive: char,
+    dead: char,
+}
+
+impl fmt::Display for Glyphs<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let game = self.game;
+
+        for row in 0..game.height {
+            if row != 0 {
+                f.write_str("\n")?;
+            }
+
+            for col in 0..game.width {
+                let alive = game.is_alive(row * game.width + col);
+                fmt::Write::write_char(f, if alive { self.alive } else { self.dead })?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An error which can be returned when parsing a [`Game`] from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseGameError {
+    line: usize,
+    reason: &'static str,
+}
+
+impl ParseGameError {
+    #[inline]
+    pub(crate) fn new(line: usize, reason: &'static str) -> ParseGameError {
+        ParseGameError { line, reason }
+    }
+
+    /// The line the error occured on, starting from `1`. This is `0` if the error isn't about a
+    /// specific line.
+    #[inline]
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ParseGameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line == 0 {
+            f.write_str(self.reason)
+        } else {
+            write!(f, "line {}: {}", self.line, self.reason)
+        }
+    }
+}
+
+impl Game {
+    /// Returns a wrapper which displays this game using specific characters for alive and dead
+    /// cells, instead of `O` and `.`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(3, 2);
+    /// game[(0, 0)] = Alive;
+    /// game[(2, 1)] = Alive;
+    ///
+    /// assert_eq!(game.glyphs('█', ' ').to_string(), "█  \n  █");
+    /// ```
+    pub fn glyphs(&self, alive: char, dead: char) -> Glyphs<'_> {
+        Glyphs {
+            game: self,
+            alive,
+            dead,
+        }
+    }
+
+    /// Parses a game written with specific characters for alive and dead cells, in the same
+    /// format as [`FromStr`](Game#impl-FromStr-for-Game).
+    ///
+    /// # Errors
+    /// Returns an error if there are no rows, the rows have different lengths, or a row contains
+    /// any other character.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let game = Game::parse_with("#__\n_#_", '#', '_').unwrap();
+    /// assert!(game.row(1).eq([Dead, Alive, Dead]));
+    /// ```
+    pub fn parse_with(s: &str, alive: char, dead: char) -> Result<Game, ParseGameError> {
+        let error = |line, reason| Err(ParseGameError { line, reason });
+
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim_matches(|c: char| c.is_whitespace() && c != alive && c != dead);
+            if line.is_empty() {
+                continue;
+            }
+
+            let start = cells.len();
+            for c in line.chars() {
+                cells.push(match c {
+                    c if c == alive => Cell::Alive,
+                    c if c == dead => Cell::Dead,
+                    _ => return error(i + 1, "unexpected character"),
+                });
+            }
+
+            let len = cells.len() - start;
+            if height == 0 {
+                width = len;
+            } else if len != width {
+                return error(i + 1, "row has a different length than the first row");
+            }
+            height += 1;
+        }
+
+        if height == 0 {
+            return error(0, "expected at least one row");
+        }
+
+        let mut game = Game::new(width, height);
+        game.cells = cells;
+        Ok(game)
+    }
+
+    /// Writes this game as RLE, the format used by Golly and most other programs.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(4, 3);
+    /// game[(1, 0)] = Alive;
+    /// game[(2, 0)] = Alive;
+    /// game[(0, 2)] = Alive;
+    ///
+    /// assert_eq!(game.to_rle(), "x = 4, y = 3, rule = B3/S23\nb2o2$o!\n");
+    /// ```
+    pub fn to_rle(&self) -> String {
+        let rule = alloc::format!("{}", self.rule);
+        rle::encode(self.width, self.height, &rule, false, |row, col| {
+            self.is_alive(row * self.width + col) as u8
+        })
+    }
+
+    /// Parses a game from RLE, with the size and rule from its header. Without a rule, it runs
+    /// [`Rule::LIFE`].
+    ///
+    /// Any state other than dead is alive, so multi-state patterns can be read too. Anything
+    /// after a `:` in the rule, like Golly's bounded grids, is ignored.
+    ///
+    /// # Errors
+    /// Returns an error if the RLE is invalid, the rule isn't a life-like rule, the pattern is
+    /// bigger than its header says, or it is empty. The header can't ask for more than 2^28
+    /// cells, so that loading a pattern can't run out of memory just from a bogus size.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Rule};
+    /// let game = Game::from_rle("
+    ///     #N Glider
+    ///     x = 3, y = 3, rule = B3/S23
+    ///     bob$2bo$3o!
+    /// ")
+    /// .unwrap();
+    ///
+    /// assert_eq!(game.to_string(), ".O.\n..O\nOOO");
+    /// assert_eq!(game.rule(), Rule::LIFE);
+    ///
+    /// assert!(Game::from_rle("x = 100000000000, y = 100000000000\no!").is_err());
+    /// ```
+    pub fn from_rle(s: &str) -> Result<Game, ParseGameError> {
+        let pattern = rle::decode(s)?;
+
+        let rule = match pattern.rule {
+            Some(rule) => {
+                let rule = rule.split(':').next().unwrap_or_default();
+                rule.parse()
+                    .map_err(|_| ParseGameError::new(0, "unsupported rule"))?
+            }
+            None => Rule::LIFE,
+        };
+
+        if pattern.width == 0 || pattern.height == 0 {
+            return Err(ParseGameError::new(0, "pattern is empty"));
+        }
+
+        let mut game = Game::with_rule(pattern.width, pattern.height, rule);
+        for (row, col, len, _) in pattern.runs {
+            game.get_row_mut(row)[col..col + len].fill(Cell::Alive);
+        }
+
+        Ok(game)
+    }
+
+    /// Writes this game in the plaintext format used by `.cells` files, which is the same as
+    /// [`Display`](Game#impl-Display-for-Game), but with a newline after every row.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(3, 2);
+    /// game[(1, 1)] = Alive;
+    ///
+    /// assert_eq!(game.to_plaintext(), "...\n.O.\n");
+    /// ```
+    pub fn to_plaintext(&self) -> String {
+        let mut s = alloc::format!("{}", self);
+        s.push('\n');
+        s
+    }
+
+    /// Parses a game in the plaintext format used by `.cells` files, running [`Rule::LIFE`].
+    ///
+    /// Lines starting with `!` are comments. Rows can be shorter than the widest row, and blank
+    /// lines are rows of dead cells, unless they are at the end.
+    ///
+    /// # Errors
+    /// Returns an error if a row contains a character other than `.` and `O`, or the pattern is
+    /// empty.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::Game;
+    /// let game = Game::from_plaintext("!Name: Glider\n.O\n..O\nOOO\n").unwrap();
+    /// assert_eq!(game.to_string(), ".O.\n..O\nOOO");
+    /// ```
+    pub fn from_plaintext(s: &str) -> Result<Game, ParseGameError> {
+        let rows: Vec<(usize, &str)> = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim_end()))
+            .filter(|(_, line)| !line.starts_with('!'))
+            .collect();
+
+        let height = rows
+            .iter()
+            .rposition(|(_, line)| !line.is_empty())
+            .map_or(0, |i| i + 1);
+        let width = rows.iter().map(|(_, line)| line.len()).max().unwrap_or(0);
+
+        if width == 0 || height == 0 {
+            return Err(ParseGameError::new(0, "pattern is empty"));
+        }
+
+        let mut game = Game::new(width, height);
+        for (row, &(line, text)) in rows[..height].iter().enumerate() {
+            for (cell, b) in game.get_row_mut(row).iter_mut().zip(text.bytes()) {
+                *cell = match b {
+                    b'O' => Cell::Alive,
+                    b'.' => Cell::Dead,
+                    _ => return Err(ParseGameError::new(line, "unexpected character")),
+                };
+            }
+        }
+
+        Ok(game)
+    }
+}
+
+/// Writes each row as a line, with `O` for alive cells and `.` for dead cells. There is no newline
+/// after the last row.
+///
+/// # Examples
+/// ```
+/// # use cgol::{Game, Cell::*};
+/// let mut game = Game::new(3, 3);
+///
+/// // a blinker
+/// game[(0, 1)] = Alive;
+/// game[(1, 1)] = Alive;
+/// game[(2, 1)] = Alive;
+///
+/// assert_eq!(game.to_string(), "...\nOOO\n...");
+/// ```
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.glyphs('O', '.').fmt(f)
+    }
+}
+
+/// Shows the size, rule and logical cells, with each row as a string of `.` (dead) and `O`
+/// (alive).
+///
+/// # Examples
+/// ```
+/// # use cgol::{Game, Cell::*};
+/// let mut game = Game::new(3, 2);
+/// game[(1, 0)] = Alive;
+///
+/// assert_eq!(
+///     format!("{:?}", game),
+///     r#"Game { width: 3, height: 2, rule: B3/S23, cells: [".O.", "..."] }"#
+/// );
+/// ```
+impl fmt::Debug for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Shows every row as a list of strings.
+        struct Cells<'a>(&'a Game);
+
+        impl fmt::Debug for Cells<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list()
+                    .entries((0..self.0.height).map(|row| Row(self.0, row)))
+                    .finish()
+            }
+        }
+
+        /// Shows a row as a string.
+        struct Row<'a>(&'a Game, usize);
+
+        impl fmt::Debug for Row<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("\"")?;
+                for cell in self.0.row(self.1) {
+                    f.write_str(if cell == Cell::Alive { "O" } else { "." })?;
+                }
+                f.write_str("\"")
+            }
+        }
+
+        f.debug_struct("Game")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("rule", &format_args!("{}", self.rule))
+            .field("cells", &Cells(self))
+            .finish()
+    }
+}
+
+/// Parses the format written by [`Display`](Game#impl-Display-for-Game), running
+/// [`Rule::LIFE`](crate::Rule::LIFE). Blank lines and whitespace around each row are
+/// ignored, unless it is used as a glyph.
+///
+/// # Examples
+/// ```
+/// # use cgol::Game;
+/// let mut game: Game = "
+///     .....
+///     ..O..
+///     ..O..
+///     ..O..
+///     .....
+/// "
+/// .parse()
+/// .unwrap();
+///
+/// game.tick();
+/// assert_eq!(game.to_string(), ".....\n.....\n.OOO.\n.....\n.....");
+/// ```
+impl FromStr for Game {
+    type Err = ParseGameError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Game::parse_with(s, 'O', '.')
+    }
+}
@@ -0,0 +1,577 @@
+//! Contains [`Game`]'s optional undo layer, which records changes so that they can be undone and
+//! redone.
+
+use super::Game;
+use crate::cell::Cell;
+use alloc::{collections::VecDeque, vec, vec::Vec};
+use core::mem::size_of;
+
+/// Which cells flipped, in whichever form is smaller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Delta {
+    /// The sorted indexes of the cells which flipped.
+    Sparse(Vec<usize>),
+    /// Bit `i % 64` of word `i / 64` is set if cell `i` flipped.
+    Dense(Vec<u64>),
+}
+
+/// Gets how many words a [`Delta::Dense`] over `len` cells has.
+fn words(len: usize) -> usize {
+    (len - 1) / 64 + 1
+}
+
+impl Delta {
+    /// Finds the cells out of `len` for which `flipped` returns `true`.
+    fn new(len: usize, flipped: impl Fn(usize) -> bool) -> Delta {
+        let flipped = &flipped;
+        let changed = || (0..len).filter(move |&i| flipped(i));
+        let words = words(len);
+
+        if changed().nth(words).is_none() {
+            Delta::Sparse(changed().collect())
+        } else {
+            let mut mask = vec![0; words];
+            for i in changed() {
+                mask[i / 64] |= 1 << (i % 64);
+            }
+            Delta::Dense(mask)
+        }
+    }
+
+    /// Creates a delta from the sorted indexes of the cells out of `len` which flipped.
+    fn from_indexes(indexes: Vec<usize>, len: usize) -> Delta {
+        if indexes.len() <= words(len) {
+            Delta::Sparse(indexes)
+        } else {
+            let mut mask = vec![0; words(len)];
+            Delta::Sparse(indexes).flip_bits(&mut mask);
+            Delta::Dense(mask)
+        }
+    }
+
+    /// Combines two deltas over `len` cells, as if one was applied after the other.
+    fn merge(&self, other: &Delta, len: usize) -> Delta {
+        let mut mask = vec![0; words(len)];
+        self.flip_bits(&mut mask);
+        other.flip_bits(&mut mask);
+        Delta::new(len, |i| mask[i / 64] >> (i % 64) & 1 != 0)
+    }
+
+    /// Returns `true` if no cells flipped.
+    fn is_empty(&self) -> bool {
+        match self {
+            Delta::Sparse(indexes) => indexes.is_empty(),
+            Delta::Dense(_) => false,
+        }
+    }
+
+    /// Flips the bits of the cells which flipped in a mask like [`Delta::Dense`]'s.
+    fn flip_bits(&self, mask: &mut [u64]) {
+        match self {
+            Delta::Sparse(indexes) => {
+                for &i in indexes {
+                    mask[i / 64] ^= 1 << (i % 64);
+                }
+            }
+            Delta::Dense(words) => {
+                for (word, flipped) in mask.iter_mut().zip(words) {
+                    *word ^= flipped;
+                }
+            }
+        }
+    }
+
+    /// Flips the cells in a buffer.
+    fn apply(&self, cells: &mut [Cell]) {
+        match self {
+            Delta::Sparse(indexes) => {
+                for &i in indexes {
+                    cells[i] = !cells[i];
+                }
+            }
+            Delta::Dense(mask) => {
+                for (i, cell) in cells.iter_mut().enumerate() {
+                    if mask[i / 64] >> (i % 64) & 1 != 0 {
+                        *cell = !*cell;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Gets roughly how many bytes this takes up.
+    fn size(&self) -> usize {
+        size_of::<Entry>()
+            + match self {
+                Delta::Sparse(indexes) => indexes.len() * size_of::<usize>(),
+                Delta::Dense(mask) => mask.len() * size_of::<u64>(),
+            }
+    }
+}
+
+/// A change which can be undone, either a batch of edits or a tick.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    /// Which cells flipped in the logical state.
+    delta: Delta,
+    /// The generation before the change.
+    before: u64,
+    /// The generation after the change.
+    after: u64,
+}
+
+/// Cells which were borrowed mutably: `count` cells starting at index `start`, each `step` apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Span {
+    start: usize,
+    step: usize,
+    count: usize,
+}
+
+/// The recorded changes, and the edits which haven't been recorded yet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Undo {
+    /// The cells which were borrowed mutably since the last recorded change.
+    spans: Vec<Span>,
+    /// The logical values of the cells in `spans` before they were borrowed, in order.
+    old: Vec<Cell>,
+    /// Whether the last entry is a batch of edits which later edits are added to, until the next
+    /// tick, checkpoint, undo or redo.
+    open: bool,
+    /// The generation as of the last recorded change.
+    generation: u64,
+    /// Entries before `cursor` can be undone, and entries after it can be redone.
+    entries: VecDeque<Entry>,
+    cursor: usize,
+    /// How many bytes the entries take up, and how many they are allowed to.
+    used: usize,
+    budget: usize,
+}
+
+impl Undo {
+    /// Drops entries until they fit within the budget, starting with the oldest ones which can be
+    /// undone, then the furthest ones which can be redone.
+    fn shrink(&mut self) {
+        while self.used > self.budget {
+            let entry = if self.cursor > 0 {
+                self.cursor -= 1;
+                self.entries.pop_front()
+            } else {
+                self.entries.pop_back()
+            };
+
+            match entry {
+                Some(entry) => self.used -= entry.delta.size(),
+                None => break,
+            }
+        }
+
+        // the open batch is the newest entry, so it's only dropped once there are none left
+        if self.entries.is_empty() {
+            self.open = false;
+        }
+    }
+
+    /// Adds an entry, forgetting any undone entries.
+    fn push(&mut self, entry: Entry) {
+        for entry in self.entries.drain(self.cursor..) {
+            self.used -= entry.delta.size();
+        }
+
+        self.used += entry.delta.size();
+        self.entries.push_back(entry);
+        self.cursor = self.entries.len();
+        self.open = false;
+        self.shrink();
+    }
+
+    /// Adds edits over `len` cells to the open batch of edits, or starts a new one.
+    fn push_edits(&mut self, delta: Delta, len: usize) {
+        if delta.is_empty() {
+            return;
+        }
+
+        if self.open {
+            if let Some(entry) = self.entries.back_mut() {
+                self.used -= entry.delta.size();
+                entry.delta = entry.delta.merge(&delta, len);
+                self.used += entry.delta.size();
+
+                // the edits cancelled out
+                if entry.delta.is_empty() {
+                    self.used -= entry.delta.size();
+                    self.entries.pop_back();
+                    self.cursor = self.entries.len();
+                    self.open = false;
+                }
+                self.shrink();
+                return;
+            }
+        }
+
+        self.push(Entry {
+            delta,
+            before: self.generation,
+            after: self.generation,
+        });
+        self.open = !self.entries.is_empty();
+    }
+}
+
+impl Game {
+    /// Starts recording changes so they can be [undone](Game::undo), keeping at most roughly
+    /// `budget` bytes of them.
+    ///
+    /// Ticks and edits are recorded as the cells which flipped, so they take up less memory the
+    /// less they change. Edits made between ticks are grouped together, unless they are split up
+    /// by [`checkpoint`](Game::checkpoint). Once the budget is used up, the oldest changes are
+    /// forgotten, and if everything has been undone, the last ones to be redone are.
+    ///
+    /// Edits are found by remembering the old values of cells as they're borrowed mutably, through
+    /// [`IndexMut`](core::ops::IndexMut), [`get_mut`](Game::get_mut),
+    /// [`get_row_mut`](Game::get_row_mut), [`row_mut`](Game::row_mut) or
+    /// [`col_mut`](Game::col_mut), so no copy of the cells is kept. Because of this, borrowing a
+    /// cell mutably counts as an edit for [`can_undo`](Game::can_undo) and
+    /// [`can_redo`](Game::can_redo), even if it isn't changed.
+    ///
+    /// Only the logical cells and the [generation](Game::generation) are restored. In particular,
+    /// the previous generation used by [`diff`](Game::diff) and
+    /// [`tick_reversible`](Game::tick_reversible) isn't.
+    ///
+    /// If changes are already being recorded, this only changes the budget.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(5, 5);
+    /// game.enable_undo(1 << 20);
+    ///
+    /// game[(2, 2)] = Alive;
+    /// game.tick();
+    /// assert!(game.all_dead());
+    ///
+    /// // undo the tick, then the edit
+    /// assert!(game.undo());
+    /// assert_eq!(game[(2, 2)], Alive);
+    /// assert!(game.undo());
+    /// assert!(game.all_dead());
+    /// assert!(!game.undo());
+    ///
+    /// assert!(game.redo());
+    /// assert_eq!(game[(2, 2)], Alive);
+    /// ```
+    ///
+    /// Shrinking the budget after undoing everything keeps the changes which would be redone
+    /// first:
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(64, 64);
+    /// game.enable_undo(1 << 20);
+    ///
+    /// // a small edit, then a big one
+    /// game[(0, 0)] = Alive;
+    /// game.checkpoint();
+    /// for row in 0..64 {
+    ///     game.row_mut(row).for_each(|cell| *cell = Alive);
+    /// }
+    ///
+    /// assert!(game.undo());
+    /// assert!(game.undo());
+    /// assert!(!game.can_undo());
+    ///
+    /// // only leave room for the small edit
+    /// game.enable_undo(256);
+    /// assert!(game.redo());
+    /// assert_eq!(game.population(), 1);
+    /// assert!(!game.redo());
+    /// ```
+    pub fn enable_undo(&mut self, budget: usize) {
+        match &mut self.undo {
+            Some(undo) => {
+                undo.budget = budget;
+                undo.shrink();
+            }
+            None => {
+                self.undo = Some(Undo {
+                    spans: Vec::new(),
+                    old: Vec::new(),
+                    open: false,
+                    generation: self.generation,
+                    entries: VecDeque::new(),
+                    cursor: 0,
+                    used: 0,
+                    budget,
+                })
+            }
+        }
+    }
+
+    /// Stops recording changes, and forgets the ones which were recorded.
+    pub fn disable_undo(&mut self) {
+        self.undo = None;
+    }
+
+    /// Returns `true` if changes are being recorded.
+    #[inline]
+    pub fn has_undo(&self) -> bool {
+        self.undo.is_some()
+    }
+
+    /// Records the edits made since the last tick or checkpoint as their own change, so that
+    /// they're undone separately from later edits.
+    pub fn checkpoint(&mut self) {
+        self.record_undo();
+    }
+
+    /// Returns `true` if there is a change which can be undone.
+    ///
+    /// This doesn't look at the cells, so it's `true` after borrowing a cell mutably even if it
+    /// wasn't changed.
+    pub fn can_undo(&self) -> bool {
+        match &self.undo {
+            Some(undo) => undo.cursor > 0 || self.has_unrecorded(undo),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if there is a change which can be redone.
+    pub fn can_redo(&self) -> bool {
+        match &self.undo {
+            Some(undo) => undo.cursor < undo.entries.len() && !self.has_unrecorded(undo),
+            None => false,
+        }
+    }
+
+    /// Returns `true` if anything may have changed since the last recorded change.
+    fn has_unrecorded(&self, undo: &Undo) -> bool {
+        !undo.spans.is_empty() || undo.generation != self.generation
+    }
+
+    /// Undoes the last change, returning `false` if there isn't one.
+    ///
+    /// Edits made since the last tick or checkpoint count as a change.
+    pub fn undo(&mut self) -> bool {
+        self.record_undo();
+
+        let undo = match &mut self.undo {
+            Some(undo) if undo.cursor > 0 => undo,
+            _ => return false,
+        };
+
+        // flipping the stored cells flips the logical state too, whether or not they're inverted
+        undo.cursor -= 1;
+        let entry = &undo.entries[undo.cursor];
+        entry.delta.apply(&mut self.cells);
+        self.generation = entry.before;
+        undo.generation = entry.before;
+        true
+    }
+
+    /// Redoes the last undone change, returning `false` if there isn't one.
+    ///
+    /// Making a new change, such as editing a cell or ticking, forgets every undone change.
+    pub fn redo(&mut self) -> bool {
+        self.record_undo();
+
+        let undo = match &mut self.undo {
+            Some(undo) if undo.cursor < undo.entries.len() => undo,
+            _ => return false,
+        };
+
+        let entry = &undo.entries[undo.cursor];
+        undo.cursor += 1;
+        entry.delta.apply(&mut self.cells);
+        self.generation = entry.after;
+        undo.generation = entry.after;
+        true
+    }
+
+    /// Undoes or redoes changes until reaching the latest recorded state at a specific
+    /// generation, returning `false` if it couldn't be reached.
+    ///
+    /// When going backwards, edits made after that generation's tick are kept. If the generation
+    /// can't be reached, nothing is undone or redone.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*};
+    /// let mut game = Game::new(5, 5);
+    /// game.enable_undo(1 << 20);
+    ///
+    /// // a blinker
+    /// game[(1, 2)] = Alive;
+    /// game[(2, 2)] = Alive;
+    /// game[(3, 2)] = Alive;
+    /// let original = game.clone();
+    ///
+    /// for _ in 0..5 {
+    ///     game.tick();
+    /// }
+    ///
+    /// assert!(game.rewind_to(0));
+    /// assert!(game.row(2).eq(original.row(2)));
+    /// assert!(game.rewind_to(3));
+    /// assert_eq!(game.generation(), 3);
+    /// assert!(!game.rewind_to(6));
+    ///
+    /// // generations 4 to 9 were skipped, so they can't be reached
+    /// game.set_generation(10);
+    /// assert!(!game.rewind_to(7));
+    /// assert_eq!(game.generation(), 10);
+    /// ```
+    pub fn rewind_to(&mut self, generation: u64) -> bool {
+        if self.generation != generation {
+            self.record_undo();
+            if !self.can_rewind_to(generation) {
+                return false;
+            }
+        }
+
+        while self.generation > generation && self.undo() {}
+        while self.generation < generation && self.redo() {}
+
+        // redo edits made at this generation
+        while let Some(undo) = &self.undo {
+            let is_edit = match undo.entries.get(undo.cursor) {
+                Some(entry) => entry.before == entry.after && entry.after == generation,
+                None => false,
+            };
+
+            if !is_edit || !self.redo() {
+                break;
+            }
+        }
+
+        self.generation == generation
+    }
+
+    /// Returns `true` if undoing or redoing the recorded changes reaches a specific generation.
+    fn can_rewind_to(&self, generation: u64) -> bool {
+        let undo = match &self.undo {
+            Some(undo) => undo,
+            None => return false,
+        };
+
+        // undoing stops at the first generation at or before the target, and redoing at or after
+        let reached = if generation < self.generation {
+            let undone = undo.entries.range(..undo.cursor).rev();
+            undone.map(|entry| entry.before).find(|&g| g <= generation)
+        } else {
+            let redone = undo.entries.range(undo.cursor..);
+            redone.map(|entry| entry.after).find(|&g| g >= generation)
+        };
+        reached == Some(generation)
+    }
+
+    /// Records the changes made since the last time this was called, forgetting any undone
+    /// changes if there were some. Later edits start a new batch.
+    pub(super) fn record_undo(&mut self) {
+        self.record_edits();
+
+        let undo = match &mut self.undo {
+            Some(undo) => undo,
+            None => return,
+        };
+
+        if undo.generation != self.generation {
+            undo.push(Entry {
+                delta: Delta::Sparse(Vec::new()),
+                before: undo.generation,
+                after: self.generation,
+            });
+            undo.generation = self.generation;
+        }
+        undo.open = false;
+    }
+
+    /// Records a tick from the previous generation, stored in `next`, to the current one, ending
+    /// at generation `after`. This must be called while both are still there, and after
+    /// [`record_undo`](Game::record_undo).
+    pub(super) fn record_undo_tick(&mut self, after: u64) {
+        let undo = match &mut self.undo {
+            Some(undo) => undo,
+            None => return,
+        };
+
+        let (prev, cells) = (&self.next, &self.cells);
+        let flip = self.prev_inverted != self.inverted;
+        let delta = Delta::new(cells.len(), |i| (prev[i] != cells[i]) ^ flip);
+        if delta.is_empty() && undo.generation == after {
+            return;
+        }
+
+        undo.push(Entry {
+            delta,
+            before: undo.generation,
+            after,
+        });
+        undo.generation = after;
+    }
+
+    /// Remembers the old values of `count` cells starting at index `start`, each `step` apart,
+    /// before they're borrowed mutably. The cells must not be inverted.
+    pub(super) fn record_undo_edit(&mut self, start: usize, step: usize, count: usize) {
+        // keep the pending edits to a fraction of the size of the cells
+        let limit = self.cells.len() / 64 + 64;
+        match &self.undo {
+            Some(undo) if undo.old.len() + count > limit => self.record_edits(),
+            Some(_) => {}
+            None => return,
+        }
+
+        if let Some(undo) = &mut self.undo {
+            let span = Span { start, step, count };
+            let cells = &self.cells;
+
+            undo.old
+                .extend((0..count).map(|i| cells[span.start + i * span.step]));
+            undo.spans.push(span);
+        }
+    }
+
+    /// Records a change to the whole board as an edit, before it's made. `flipped` gets whether
+    /// each cell's logical state will flip, by its index.
+    pub(super) fn record_undo_board(&mut self, flipped: impl Fn(&Game, usize) -> bool) {
+        if self.undo.is_none() {
+            return;
+        }
+
+        self.record_edits();
+        let len = self.cells.len();
+        let delta = Delta::new(len, |i| flipped(self, i));
+
+        if let Some(undo) = &mut self.undo {
+            undo.push_edits(delta, len);
+        }
+    }
+
+    /// Adds the pending edits to the open batch of edits.
+    fn record_edits(&mut self) {
+        let undo = match &mut self.undo {
+            Some(undo) if !undo.spans.is_empty() => undo,
+            _ => return,
+        };
+
+        let mut old = Vec::with_capacity(undo.old.len());
+        let mut values = undo.old.iter();
+        for span in undo.spans.drain(..) {
+            for i in 0..span.count {
+                old.push((span.start + i * span.step, *values.next().unwrap()));
+            }
+        }
+        undo.old.clear();
+
+        // the first old value of each cell is from before all of the edits
+        old.sort_by_key(|&(i, _)| i);
+        old.dedup_by_key(|&mut (i, _)| i);
+
+        let (cells, inverted) = (&self.cells, self.inverted);
+        let indexes = old
+            .into_iter()
+            .filter(|&(i, cell)| (cells[i] != cell) ^ inverted)
+            .map(|(i, _)| i)
+            .collect();
+
+        undo.push_edits(Delta::from_indexes(indexes, cells.len()), cells.len());
+    }
+}
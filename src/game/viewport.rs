@@ -0,0 +1,213 @@
+//! Contains [`Viewport`], which summarizes the cells in a rectangle as pixels, for rendering.
+
+use super::Game;
+use crate::{cell::Cell, Rect};
+use alloc::{vec, vec::Vec};
+
+/// How many pixels each cell of a [`Viewport`] covers, or how many cells each pixel covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Zoom {
+    /// Each cell covers `n` by `n` pixels, so `In(1)` is one pixel per cell.
+    In(usize),
+    /// Each pixel covers `2^n` by `2^n` cells, so `Out(0)` is one pixel per cell.
+    Out(u32),
+}
+
+impl Default for Zoom {
+    #[inline]
+    fn default() -> Self {
+        Zoom::In(1)
+    }
+}
+
+/// A rectangle of cells in a [`Game`], seen at a specific [`Zoom`].
+///
+/// Each pixel's value is computed from the cells it covers by going through the rows directly, so
+/// even zoomed far out, every cell is only looked at once. Pixels on the right and bottom edges
+/// may cover fewer cells, if the rectangle isn't a multiple of the zoom.
+///
+/// This is created by the [`Game::viewport`] method.
+///
+/// # Examples
+/// ```
+/// # use cgol::{life, Rect, game::Zoom};
+/// let game = life![
+///     "O...",
+///     "....",
+///     "..OO",
+///     "..OO",
+/// ];
+///
+/// let view = game.viewport(Rect::new(0, 0, 4, 4), Zoom::Out(1));
+/// assert_eq!((view.width(), view.height()), (2, 2));
+/// assert_eq!(view.any_alive(), [true, false, false, true]);
+/// assert_eq!(view.density(), [0.25, 0.0, 0.0, 1.0]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Viewport<'a> {
+    game: &'a Game,
+    rect: Rect,
+    zoom: Zoom,
+}
+
+impl Viewport<'_> {
+    /// Gets the cells this viewport covers, which are inside the game.
+    #[inline]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Gets how far this viewport is zoomed.
+    #[inline]
+    pub fn zoom(&self) -> Zoom {
+        self.zoom
+    }
+
+    /// Gets how many cells wide and tall each pixel is, when zoomed out.
+    fn block(&self) -> usize {
+        match self.zoom {
+            Zoom::In(_) => 1,
+            Zoom::Out(n) => 1usize.checked_shl(n).unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Gets how many pixels wide and tall each cell is, when zoomed in.
+    fn scale(&self) -> usize {
+        match self.zoom {
+            Zoom::In(n) => n,
+            Zoom::Out(_) => 1,
+        }
+    }
+
+    /// Gets this viewport's width in pixels.
+    ///
+    /// # Panics
+    /// Panics if the width overflows, which can only happen when zoomed in.
+    pub fn width(&self) -> usize {
+        self.pixels(self.rect.width)
+    }
+
+    /// Gets this viewport's height in pixels.
+    ///
+    /// # Panics
+    /// Panics if the height overflows, which can only happen when zoomed in.
+    pub fn height(&self) -> usize {
+        self.pixels(self.rect.height)
+    }
+
+    /// Gets how many pixels cover a number of cells.
+    fn pixels(&self, cells: usize) -> usize {
+        match cells {
+            0 => 0,
+            cells => ((cells - 1) / self.block() + 1)
+                .checked_mul(self.scale())
+                .expect("viewport size overflow"),
+        }
+    }
+
+    /// Gets whether any cell each pixel covers is alive, row by row.
+    ///
+    /// # Panics
+    /// Panics if the number of pixels overflows, which can only happen when zoomed in.
+    pub fn any_alive(&self) -> Vec<bool> {
+        self.sample(|alive, _| alive > 0)
+    }
+
+    /// Gets the fraction of the cells each pixel covers which are alive, from `0.0` to `1.0`, row
+    /// by row.
+    ///
+    /// # Panics
+    /// Panics if the number of pixels overflows, which can only happen when zoomed in.
+    pub fn density(&self) -> Vec<f32> {
+        self.sample(|alive, cells| alive as f32 / cells as f32)
+    }
+
+    /// Counts the alive cells each pixel covers, then converts each count with `f`, which also
+    /// gets how many cells the pixel covers.
+    fn sample<T: Copy + Default>(&self, f: impl Fn(usize, usize) -> T) -> Vec<T> {
+        let game = self.game;
+        let Rect {
+            x,
+            y,
+            width,
+            height,
+        } = self.rect;
+        let block = self.block();
+
+        if width == 0 || height == 0 {
+            return Vec::new();
+        }
+
+        // counts for each block, before zooming in
+        let cols = (width - 1) / block + 1;
+        let rows = (height - 1) / block + 1;
+        let mut counts = vec![0; cols * rows];
+
+        for row in y..y + height {
+            let start = row * game.width + x;
+            let cells = &game.cells[start..start + width];
+            let counts = &mut counts[(row - y) / block * cols..][..cols];
+
+            for (count, cells) in counts.iter_mut().zip(cells.chunks(block)) {
+                *count += cells.iter().filter(|&&cell| cell == Cell::Alive).count();
+            }
+        }
+
+        let scale = self.scale();
+        let (pixel_width, pixel_height) = (self.width(), self.height());
+        let len = pixel_width
+            .checked_mul(pixel_height)
+            .expect("viewport size overflow");
+        let mut pixels = vec![T::default(); len];
+
+        for (i, &count) in counts.iter().enumerate() {
+            let (row, col) = (i / cols, i % cols);
+            let block_width = block.min(width - col * block);
+            let block_height = block.min(height - row * block);
+            let cells = block_width * block_height;
+
+            let alive = if game.inverted { cells - count } else { count };
+            let value = f(alive, cells);
+
+            for pixel_row in row * scale..(row + 1) * scale {
+                let start = pixel_row * pixel_width + col * scale;
+                pixels[start..start + scale].fill(value);
+            }
+        }
+
+        pixels
+    }
+}
+
+impl Game {
+    /// Returns a view of the cells in a rectangle, at a specific zoom. Parts of the rectangle
+    /// outside of this game are left out.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*, Rect, game::Zoom};
+    /// // every other column is alive
+    /// let mut game = Game::new(1000, 1000);
+    /// for row in 0..1000 {
+    ///     for col in (0..1000).step_by(2) {
+    ///         game[(col, row)] = Alive;
+    ///     }
+    /// }
+    ///
+    /// // the whole game, in a 125x125 canvas
+    /// let view = game.viewport(Rect::new(0, 0, 1000, 1000), Zoom::Out(3));
+    /// assert_eq!((view.width(), view.height()), (125, 125));
+    /// assert!(view.density().iter().all(|&d| d == 0.5));
+    ///
+    /// // zoomed in on the corner, where each cell is 2x2 pixels
+    /// let view = game.viewport(Rect::new(0, 0, 2, 1), Zoom::In(2));
+    /// assert_eq!(view.any_alive(), [true, true, false, false, true, true, false, false]);
+    /// ```
+    pub fn viewport(&self, rect: Rect, zoom: Zoom) -> Viewport<'_> {
+        Viewport {
+            game: self,
+            rect: rect.clamp(self.width, self.height),
+            zoom,
+        }
+    }
+}
@@ -0,0 +1,368 @@
+//! Contains [`GifRecorder`], which records a [`Game`] ticking as an animated GIF.
+
+use super::{Image, ImageOptions, Rgb};
+use crate::{Game, Rect};
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+use core::{convert::TryFrom, fmt};
+use std::io::{self, Error, ErrorKind, Write};
+
+/// How many times an animation repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Repeat {
+    /// Loop forever.
+    Infinite,
+    /// Repeat a specific number of times after playing once, so `Finite(0)` only plays once.
+    Finite(u16),
+}
+
+impl Default for Repeat {
+    #[inline]
+    fn default() -> Self {
+        Repeat::Infinite
+    }
+}
+
+/// How to record a [`Game`] into an animated GIF.
+///
+/// The scale and crop window are set in [`image`](GifOptions::image), as
+/// [`cell_size`](ImageOptions::cell_size) and [`crop`](ImageOptions::crop).
+///
+/// # Examples
+/// ```
+/// # use cgol::{Rect, image::{gif::{GifOptions, Repeat}, ImageOptions}};
+/// let options = GifOptions {
+///     image: ImageOptions {
+///         cell_size: 4,
+///         crop: Some(Rect::new(10, 10, 32, 32)),
+///         ..ImageOptions::default()
+///     },
+///     delay: 5,
+///     repeat: Repeat::Finite(2),
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GifOptions {
+    /// How to render each frame.
+    pub image: ImageOptions,
+    /// How long each frame is shown, in hundredths of a second. Defaults to `10`.
+    pub delay: u16,
+    /// How many times the animation repeats. Defaults to [`Repeat::Infinite`].
+    pub repeat: Repeat,
+}
+
+impl Default for GifOptions {
+    fn default() -> Self {
+        GifOptions {
+            image: ImageOptions::default(),
+            delay: 10,
+            repeat: Repeat::Infinite,
+        }
+    }
+}
+
+/// Records frames of a [`Game`] into an animated GIF.
+///
+/// Only the part of each frame which changed since the last one is written, and pixels inside
+/// that part which didn't change are transparent, so recording a mostly still board is cheap.
+///
+/// Frames can use at most 255 colors. When a [`ColorMap`](super::ColorMap) gives more than that,
+/// the extra colors are drawn as the closest of the first 255.
+///
+/// # Examples
+/// ```
+/// # use cgol::{Game, Cell::*, image::gif::{GifOptions, GifRecorder}};
+/// let mut game = Game::new(5, 5);
+///
+/// // a blinker
+/// game[(1, 2)] = Alive;
+/// game[(2, 2)] = Alive;
+/// game[(3, 2)] = Alive;
+///
+/// let mut recorder = GifRecorder::new(Vec::new(), &game, &GifOptions::default())?;
+/// recorder.record(&mut game, 2)?;
+/// let gif = recorder.finish()?;
+///
+/// assert!(gif.starts_with(b"GIF89a"));
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct GifRecorder<W: Write> {
+    encoder: ::gif::Encoder<W>,
+    options: GifOptions,
+    previous: Image,
+}
+
+impl<W: Write> fmt::Debug for GifRecorder<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GifRecorder")
+            .field("options", &self.options)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<W: Write> GifRecorder<W> {
+    /// Starts recording into a writer, with a game's current state as the first frame.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails, or if the frames are empty or more than `65535` pixels
+    /// wide or tall.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, image::gif::{GifOptions, GifRecorder}};
+    /// let game = Game::new(100, 100);
+    /// let mut options = GifOptions::default();
+    /// options.image.cell_size = 1000;
+    ///
+    /// // the size is checked before anything is rendered
+    /// assert!(GifRecorder::new(Vec::new(), &game, &options).is_err());
+    /// ```
+    pub fn new(writer: W, game: &Game, options: &GifOptions) -> io::Result<Self> {
+        let invalid = || {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "gif images must be between 1 and 65535 pixels wide and tall",
+            )
+        };
+        let size = |n| match u16::try_from(n) {
+            Ok(n) if n != 0 => Ok(n),
+            _ => Err(invalid()),
+        };
+
+        let (width, height) = options.image.image_size(game).ok_or_else(invalid)?;
+        let mut encoder =
+            ::gif::Encoder::new(writer, size(width)?, size(height)?, &[]).map_err(to_io)?;
+        encoder
+            .set_repeat(match options.repeat {
+                Repeat::Infinite => ::gif::Repeat::Infinite,
+                Repeat::Finite(n) => ::gif::Repeat::Finite(n),
+            })
+            .map_err(to_io)?;
+
+        let mut recorder = GifRecorder {
+            encoder,
+            options: options.clone(),
+            previous: Image::new(0, 0, [0; 3]),
+        };
+
+        let image = game.render(&options.image);
+        let all = Rect::new(0, 0, image.width, image.height);
+        recorder.write(&image, all, false)?;
+        recorder.previous = image;
+        Ok(recorder)
+    }
+
+    /// Adds a game's current state as the next frame.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails, or if the frame isn't the same size as the first one.
+    pub fn add_frame(&mut self, game: &Game) -> io::Result<()> {
+        let size = Some((self.previous.width, self.previous.height));
+        if self.options.image.image_size(game) != size {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "every frame must be the same size as the first one",
+            ));
+        }
+
+        let image = game.render(&self.options.image);
+
+        match self.changed(&image) {
+            Some(rect) => self.write(&image, rect, true)?,
+            None => {
+                // an empty frame, so the delay still passes
+                self.encoder
+                    .write_frame(&::gif::Frame {
+                        delay: self.options.delay,
+                        width: 1,
+                        height: 1,
+                        transparent: Some(0),
+                        palette: Some(vec![0; 3]),
+                        buffer: vec![0].into(),
+                        ..::gif::Frame::default()
+                    })
+                    .map_err(to_io)?;
+            }
+        }
+
+        self.previous = image;
+        Ok(())
+    }
+
+    /// Ticks a game a specific number of times, adding a frame after every tick.
+    ///
+    /// # Errors
+    /// Returns an error if adding a frame fails. See [`add_frame`](GifRecorder::add_frame).
+    pub fn record(&mut self, game: &mut Game, generations: usize) -> io::Result<()> {
+        for _ in 0..generations {
+            game.tick();
+            self.add_frame(game)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes the GIF, returning the writer.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails.
+    pub fn finish(self) -> io::Result<W> {
+        self.encoder.into_inner()
+    }
+
+    /// Gets the smallest rectangle of pixels which contains every pixel that changed since the last
+    /// frame, or `None` if nothing changed.
+    fn changed(&self, image: &Image) -> Option<Rect> {
+        let row_len = image.width * 3;
+        let rows = image.pixels.chunks_exact(row_len);
+        let previous = self.previous.pixels.chunks_exact(row_len);
+
+        let mut rect: Option<(usize, usize, usize, usize)> = None;
+        for (y, (row, previous)) in rows.zip(previous).enumerate() {
+            if row == previous {
+                continue;
+            }
+
+            let pixels = || row.chunks_exact(3).zip(previous.chunks_exact(3));
+            let left = pixels().position(|(a, b)| a != b).unwrap_or(0);
+            let right = image.width - 1 - pixels().rev().position(|(a, b)| a != b).unwrap_or(0);
+
+            rect = Some(match rect {
+                Some((top, l, _, r)) => (top, l.min(left), y, r.max(right)),
+                None => (y, left, y, right),
+            });
+        }
+
+        rect.map(|(top, left, bottom, right)| {
+            Rect::new(left, top, right - left + 1, bottom - top + 1)
+        })
+    }
+
+    /// Writes part of an image as a frame. With `delta`, pixels which haven't changed since the
+    /// last frame are transparent.
+    fn write(&mut self, image: &Image, rect: Rect, delta: bool) -> io::Result<()> {
+        let mut palette = Vec::new();
+        let mut indices = BTreeMap::new();
+        let mut buffer = Vec::with_capacity(rect.width * rect.height);
+
+        // a placeholder for the transparent color, which is added to the palette after the rest,
+        // so there is only room for 255 others
+        let transparent = u8::MAX;
+        let mut any_transparent = false;
+        let mut last: Option<(Rgb, u8)> = None;
+
+        for y in rect.y..rect.y + rect.height {
+            let start = (y * image.width + rect.x) * 3;
+            let end = start + rect.width * 3;
+
+            for (x, pixel) in image.pixels[start..end].chunks_exact(3).enumerate() {
+                if delta && self.previous.pixels[start + x * 3..start + x * 3 + 3] == *pixel {
+                    buffer.push(transparent);
+                    any_transparent = true;
+                    continue;
+                }
+
+                let color = [pixel[0], pixel[1], pixel[2]];
+                let index = match last {
+                    Some((last, index)) if last == color => index,
+                    _ => *indices.entry(color).or_insert_with(|| {
+                        if palette.len() < 255 * 3 {
+                            palette.extend_from_slice(&color);
+                            (palette.len() / 3 - 1) as u8
+                        } else {
+                            closest(&palette, color)
+                        }
+                    }),
+                };
+                last = Some((color, index));
+                buffer.push(index);
+            }
+        }
+
+        let transparent = if any_transparent {
+            let index = palette.len() / 3;
+            for i in &mut buffer {
+                if *i == transparent {
+                    *i = index as u8;
+                }
+            }
+            palette.extend_from_slice(&[0; 3]);
+            Some(index as u8)
+        } else {
+            None
+        };
+
+        self.encoder
+            .write_frame(&::gif::Frame {
+                delay: self.options.delay,
+                left: rect.x as u16,
+                top: rect.y as u16,
+                width: rect.width as u16,
+                height: rect.height as u16,
+                transparent,
+                palette: Some(palette),
+                buffer: buffer.into(),
+                ..::gif::Frame::default()
+            })
+            .map_err(to_io)
+    }
+}
+
+/// Finds the closest color in a palette.
+fn closest(palette: &[u8], color: Rgb) -> u8 {
+    let distance = |other: &[u8]| -> u32 {
+        other
+            .iter()
+            .zip(&color)
+            .map(|(&a, &b)| (a as i32 - b as i32).pow(2) as u32)
+            .sum()
+    };
+
+    palette
+        .chunks_exact(3)
+        .enumerate()
+        .min_by_key(|(_, other)| distance(other))
+        .map_or(0, |(i, _)| i as u8)
+}
+
+/// Converts an encoding error into an IO error.
+fn to_io(error: ::gif::EncodingError) -> Error {
+    match error {
+        ::gif::EncodingError::Io(error) => error,
+        error => Error::new(ErrorKind::InvalidData, error),
+    }
+}
+
+impl Game {
+    /// Records this game ticking a specific number of times into an animated GIF, with its
+    /// current state as the first frame. The game is left at the last frame.
+    ///
+    /// This is a shortcut for [`GifRecorder`], returning the writer when it's finished.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails, or if the frames are empty or more than `65535` pixels
+    /// wide or tall.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{life, image::gif::GifOptions};
+    /// let mut game = life![
+    ///     "..........",
+    ///     "...O......",
+    ///     "....O.....",
+    ///     "..OOO.....",
+    ///     "..........",
+    /// ];
+    /// let gif = game.record_gif(Vec::new(), 10, &GifOptions::default())?;
+    /// assert_eq!(game.generation(), 10);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn record_gif<W: Write>(
+        &mut self,
+        writer: W,
+        generations: usize,
+        options: &GifOptions,
+    ) -> io::Result<W> {
+        let mut recorder = GifRecorder::new(writer, self, options)?;
+        recorder.record(self, generations)?;
+        recorder.finish()
+    }
+}
@@ -0,0 +1,474 @@
+//! Contains [`ImportOptions`], which is used to create a [`Game`] from an image, such as with
+//! [`Game::from_gray`].
+
+use crate::{cell::Cell, Game};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// How to turn an image into a [`Game`].
+///
+/// Pixels darker than the threshold become alive cells, which matches how games are
+/// [rendered](Game::render) by default.
+///
+/// # Examples
+/// ```
+/// # use cgol::image::import::ImportOptions;
+/// let options = ImportOptions {
+///     dither: true,
+///     block_size: 4,
+///     ..ImportOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportOptions {
+    /// Cells with a brightness below this are alive. Defaults to `128`.
+    pub threshold: u8,
+    /// Whether to swap alive and dead cells, so bright pixels become alive cells. Defaults to
+    /// `false`.
+    pub invert: bool,
+    /// Whether to use Floyd–Steinberg dithering, which spreads the difference between each cell's
+    /// brightness and its color to the cells after it, so shades of gray become patterns of alive
+    /// and dead cells. Defaults to `false`.
+    pub dither: bool,
+    /// The width and height of the block of pixels each cell covers, which are averaged. Blocks
+    /// on the right and bottom edges may be smaller. Defaults to `1`.
+    pub block_size: usize,
+}
+
+impl Default for ImportOptions {
+    fn default() -> Self {
+        ImportOptions {
+            threshold: 128,
+            invert: false,
+            dither: false,
+            block_size: 1,
+        }
+    }
+}
+
+/// An error which can be returned when creating a [`Game`] from an image.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ImportError {
+    /// The image has no pixels.
+    Empty,
+    /// The length of the pixel buffer doesn't match the width and height.
+    WrongLength,
+    /// The file isn't a valid PBM, PGM or PPM file, for the reason given.
+    Netpbm(&'static str),
+    /// Decoding a PNG failed.
+    #[cfg(feature = "png")]
+    Png(png::DecodingError),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Empty => f.write_str("image has no pixels"),
+            ImportError::WrongLength => {
+                f.write_str("pixel buffer length doesn't match the image size")
+            }
+            ImportError::Netpbm(reason) => write!(f, "invalid netpbm file: {}", reason),
+            #[cfg(feature = "png")]
+            ImportError::Png(err) => write!(f, "invalid png file: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ImportError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "png")]
+            ImportError::Png(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "png")]
+impl From<png::DecodingError> for ImportError {
+    fn from(err: png::DecodingError) -> Self {
+        ImportError::Png(err)
+    }
+}
+
+/// Converts a color to its brightness.
+fn luma(r: u8, g: u8, b: u8) -> u8 {
+    // BT.601, the same as `Image::to_pgm`
+    ((r as u32 * 299 + g as u32 * 587 + b as u32 * 114) / 1000) as u8
+}
+
+impl Game {
+    /// Creates a game running [`Rule::LIFE`](crate::Rule::LIFE) from a grayscale image, with one
+    /// byte per pixel, row by row.
+    ///
+    /// # Errors
+    /// Returns an error if the image is empty, or `pixels` isn't `width * height` bytes long.
+    ///
+    /// # Panics
+    /// Panics if the block size is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*, image::import::ImportOptions};
+    /// let pixels = [
+    ///     0, 0, 255, 255,
+    ///     0, 200, 255, 90,
+    /// ];
+    ///
+    /// let game = Game::from_gray(4, 2, &pixels, &ImportOptions::default()).unwrap();
+    /// assert_eq!(game.to_string(), "OO..\nO..O");
+    ///
+    /// let blocks = ImportOptions {
+    ///     block_size: 2,
+    ///     ..ImportOptions::default()
+    /// };
+    /// let game = Game::from_gray(4, 2, &pixels, &blocks).unwrap();
+    /// assert_eq!(game.to_string(), "O.");
+    /// ```
+    pub fn from_gray(
+        width: usize,
+        height: usize,
+        pixels: &[u8],
+        options: &ImportOptions,
+    ) -> Result<Game, ImportError> {
+        if width == 0 || height == 0 {
+            return Err(ImportError::Empty);
+        } else if width.checked_mul(height) != Some(pixels.len()) {
+            return Err(ImportError::WrongLength);
+        }
+
+        let size = options.block_size;
+        assert!(size != 0, "block size must not be 0");
+
+        let cols = (width - 1) / size + 1;
+        let rows = (height - 1) / size + 1;
+
+        // the brightness of each cell, which dithering can push below 0 or above 255
+        let mut values: Vec<i32> = {
+            let mut sums = vec![0u64; cols * rows];
+            for (y, pixels) in pixels.chunks_exact(width).enumerate() {
+                let sums = &mut sums[y / size * cols..][..cols];
+                for (x, &pixel) in pixels.iter().enumerate() {
+                    sums[x / size] += pixel as u64;
+                }
+            }
+
+            sums.iter()
+                .enumerate()
+                .map(|(i, &sum)| {
+                    let (row, col) = (i / cols, i % cols);
+                    let block_width = size.min(width - col * size);
+                    let block_height = size.min(height - row * size);
+                    (sum / (block_width * block_height) as u64) as i32
+                })
+                .collect()
+        };
+
+        let mut game = Game::new(cols, rows);
+        for row in 0..rows {
+            for col in 0..cols {
+                let value = values[row * cols + col];
+                let dark = value < options.threshold as i32;
+                game[(col, row)] = Cell::from(dark ^ options.invert);
+
+                if options.dither {
+                    let error = value - if dark { 0 } else { 255 };
+                    let mut spread = |row: usize, col: usize, weight: i32| {
+                        if row < rows && col < cols {
+                            values[row * cols + col] += error * weight / 16;
+                        }
+                    };
+
+                    spread(row, col + 1, 7);
+                    spread(row + 1, col.wrapping_sub(1), 3);
+                    spread(row + 1, col, 5);
+                    spread(row + 1, col + 1, 1);
+                }
+            }
+        }
+
+        Ok(game)
+    }
+
+    /// Creates a game running [`Rule::LIFE`](crate::Rule::LIFE) from an RGB image, with 3 bytes
+    /// per pixel, row by row. Each pixel's brightness is used like in
+    /// [`from_gray`](Game::from_gray).
+    ///
+    /// # Errors
+    /// Returns an error if the image is empty, or `pixels` isn't `width * height * 3` bytes long.
+    ///
+    /// # Panics
+    /// Panics if the block size is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, image::import::ImportOptions};
+    /// let pixels = [255, 0, 0, 255, 255, 0, 0, 0, 255];
+    /// let game = Game::from_rgb(3, 1, &pixels, &ImportOptions::default()).unwrap();
+    /// assert_eq!(game.to_string(), "O.O");
+    /// ```
+    pub fn from_rgb(
+        width: usize,
+        height: usize,
+        pixels: &[u8],
+        options: &ImportOptions,
+    ) -> Result<Game, ImportError> {
+        if width.checked_mul(height).and_then(|n| n.checked_mul(3)) != Some(pixels.len()) {
+            return Err(ImportError::WrongLength);
+        }
+
+        let gray: Vec<u8> = pixels
+            .chunks_exact(3)
+            .map(|p| luma(p[0], p[1], p[2]))
+            .collect();
+        Game::from_gray(width, height, &gray, options)
+    }
+
+    /// Creates a game running [`Rule::LIFE`](crate::Rule::LIFE) from a PBM, PGM or PPM file, in
+    /// either the binary or the plain format.
+    ///
+    /// # Errors
+    /// Returns an error if the file isn't valid or the image is empty.
+    ///
+    /// # Panics
+    /// Panics if the block size is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, image::{import::ImportOptions, ImageOptions}};
+    /// let pbm = b"P1\n# a glider\n3 3\n0 1 0\n0 0 1\n1 1 1\n";
+    /// let game = Game::from_netpbm(pbm, &ImportOptions::default()).unwrap();
+    /// assert_eq!(game.to_string(), ".O.\n..O\nOOO");
+    ///
+    /// // rendered images can be read back
+    /// let pgm = game.render(&ImageOptions::default()).to_pgm();
+    /// assert_eq!(Game::from_netpbm(&pgm, &ImportOptions::default()).unwrap(), game);
+    /// ```
+    pub fn from_netpbm(bytes: &[u8], options: &ImportOptions) -> Result<Game, ImportError> {
+        let (width, height, gray) = Netpbm { bytes, pos: 0 }.parse()?;
+        Game::from_gray(width, height, &gray, options)
+    }
+
+    /// Creates a game running [`Rule::LIFE`](crate::Rule::LIFE) from a PNG file. Transparent
+    /// pixels are treated as if they were on a white background.
+    ///
+    /// # Errors
+    /// Returns an error if decoding fails or the image is empty.
+    ///
+    /// # Panics
+    /// Panics if the block size is `0`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*, image::{import::ImportOptions, ImageOptions}};
+    /// let mut game = Game::new(8, 8);
+    /// game[(3, 5)] = Alive;
+    ///
+    /// let png = game.render(&ImageOptions::default()).to_png();
+    /// assert_eq!(Game::from_png(&png[..], &ImportOptions::default()).unwrap(), game);
+    /// ```
+    #[cfg(feature = "png")]
+    pub fn from_png<R: std::io::Read>(
+        reader: R,
+        options: &ImportOptions,
+    ) -> Result<Game, ImportError> {
+        use png::{ColorType, Transformations};
+
+        let mut decoder = png::Decoder::new(reader);
+        decoder.set_transformations(Transformations::EXPAND | Transformations::STRIP_16);
+        let mut reader = decoder.read_info()?;
+
+        let mut buf = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf)?;
+        let (width, height) = (info.width as usize, info.height as usize);
+
+        let channels = match info.color_type {
+            ColorType::Grayscale => 1,
+            ColorType::GrayscaleAlpha => 2,
+            ColorType::Rgb => 3,
+            ColorType::Rgba => 4,
+            // palettes are expanded into rgb
+            ColorType::Indexed => unreachable!(),
+        };
+
+        let mut gray = Vec::with_capacity(width * height);
+        for row in buf.chunks_exact(info.line_size).take(height) {
+            for p in row[..width * channels].chunks_exact(channels) {
+                let (value, alpha) = match *p {
+                    [v] => (v, 255),
+                    [v, a] => (v, a),
+                    [r, g, b] => (luma(r, g, b), 255),
+                    [r, g, b, a] => (luma(r, g, b), a),
+                    _ => unreachable!(),
+                };
+
+                // blend onto white
+                let value = (value as u32 * alpha as u32 + 255 * (255 - alpha as u32)) / 255;
+                gray.push(value as u8);
+            }
+        }
+
+        Game::from_gray(width, height, &gray, options)
+    }
+}
+
+/// A parser for PBM, PGM and PPM files.
+struct Netpbm<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Netpbm<'_> {
+    /// Parses the whole file into a width, height and grayscale pixels.
+    fn parse(mut self) -> Result<(usize, usize, Vec<u8>), ImportError> {
+        let kind = match self.bytes {
+            [b'P', kind @ b'1'..=b'6', ..] => kind - b'0',
+            _ => return Err(ImportError::Netpbm("unknown magic number")),
+        };
+        self.pos = 2;
+
+        let width = self.number()?;
+        let height = self.number()?;
+        if width == 0 || height == 0 {
+            return Err(ImportError::Empty);
+        }
+        let len = width
+            .checked_mul(height)
+            .ok_or(ImportError::Netpbm("image is too big"))?;
+
+        // bitmaps don't have a max value, and black is 1
+        let max = if kind == 1 || kind == 4 {
+            1
+        } else {
+            match self.number()? {
+                max @ 1..=65535 => max,
+                _ => return Err(ImportError::Netpbm("max value must be between 1 and 65535")),
+            }
+        };
+
+        let channels = if kind == 3 || kind == 6 { 3 } else { 1 };
+        let samples = len
+            .checked_mul(channels)
+            .ok_or(ImportError::Netpbm("image is too big"))?;
+
+        let samples: Vec<usize> = match kind {
+            1 => (0..samples).map(|_| self.bit()).collect::<Result<_, _>>()?,
+            2 | 3 => (0..samples)
+                .map(|_| self.number())
+                .collect::<Result<_, _>>()?,
+            4 => {
+                self.separator()?;
+                let row_len = (width - 1) / 8 + 1;
+                let data = self.take(row_len * height)?;
+                data.chunks_exact(row_len)
+                    .flat_map(|row| {
+                        (0..width).map(move |x| (row[x / 8] >> (7 - x % 8) & 1) as usize)
+                    })
+                    .collect()
+            }
+            _ => {
+                self.separator()?;
+                if max < 256 {
+                    self.take(samples)?.iter().map(|&b| b as usize).collect()
+                } else {
+                    let data = self.take(samples * 2)?;
+                    data.chunks_exact(2)
+                        .map(|b| u16::from_be_bytes([b[0], b[1]]) as usize)
+                        .collect()
+                }
+            }
+        };
+
+        if samples.iter().any(|&sample| sample > max) {
+            return Err(ImportError::Netpbm("sample is greater than the max value"));
+        }
+
+        let scale = |sample: usize| (sample * 255 / max) as u8;
+        let gray = match kind {
+            1 | 4 => samples.iter().map(|&bit| 255 - scale(bit)).collect(),
+            2 | 5 => samples.iter().map(|&sample| scale(sample)).collect(),
+            _ => samples
+                .chunks_exact(3)
+                .map(|p| luma(scale(p[0]), scale(p[1]), scale(p[2])))
+                .collect(),
+        };
+
+        Ok((width, height, gray))
+    }
+
+    /// Skips whitespace and comments.
+    fn skip(&mut self) {
+        while let Some(&byte) = self.bytes.get(self.pos) {
+            if byte == b'#' {
+                while matches!(self.bytes.get(self.pos), Some(&b) if b != b'\n') {
+                    self.pos += 1;
+                }
+            } else if byte.is_ascii_whitespace() {
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Reads a decimal number.
+    fn number(&mut self) -> Result<usize, ImportError> {
+        self.skip();
+
+        let start = self.pos;
+        let mut number: usize = 0;
+        while let Some(&byte @ b'0'..=b'9') = self.bytes.get(self.pos) {
+            number = number
+                .checked_mul(10)
+                .and_then(|n| n.checked_add((byte - b'0') as usize))
+                .ok_or(ImportError::Netpbm("number is too big"))?;
+            self.pos += 1;
+        }
+
+        if self.pos == start {
+            return Err(ImportError::Netpbm("expected a number"));
+        }
+        Ok(number)
+    }
+
+    /// Reads a single `0` or `1` in a plain PBM, which don't need to be separated.
+    fn bit(&mut self) -> Result<usize, ImportError> {
+        self.skip();
+
+        match self.bytes.get(self.pos) {
+            Some(b'0') => {
+                self.pos += 1;
+                Ok(0)
+            }
+            Some(b'1') => {
+                self.pos += 1;
+                Ok(1)
+            }
+            _ => Err(ImportError::Netpbm("expected a 0 or 1")),
+        }
+    }
+
+    /// Reads the single whitespace character between the header and the binary data.
+    fn separator(&mut self) -> Result<(), ImportError> {
+        match self.bytes.get(self.pos) {
+            Some(byte) if byte.is_ascii_whitespace() => {
+                self.pos += 1;
+                Ok(())
+            }
+            _ => Err(ImportError::Netpbm("expected whitespace after the header")),
+        }
+    }
+
+    /// Reads some bytes.
+    fn take(&mut self, len: usize) -> Result<&[u8], ImportError> {
+        let bytes = self
+            .bytes
+            .get(self.pos..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or(ImportError::Netpbm("file is truncated"))?;
+        self.pos += len;
+        Ok(bytes)
+    }
+}
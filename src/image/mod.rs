@@ -0,0 +1,395 @@
+//! Contains [`Image`], which renders a [`Game`] as pixels, and exports it as PBM, PGM, PPM or PNG.
+//! The [`svg`] module exports vector images, the [`import`] module creates games from images, and
+//! with the `gif` feature, the `gif` module records animations.
+
+use crate::{cell::Cell, game::Zoom, Game, Rect};
+use alloc::{format, vec, vec::Vec};
+
+#[cfg(feature = "gif")]
+pub mod gif;
+pub mod import;
+pub mod svg;
+
+/// A color, as `[red, green, blue]`.
+pub type Rgb = [u8; 3];
+
+/// Colors cells by how they have changed over time, using [`Game`]'s activity layer (see
+/// [`Game::enable_activity`]).
+///
+/// Each map goes from one color to another, reaching the second color at `max`. Cells which the
+/// map doesn't apply to, and every cell if activity isn't being kept track of, use the normal
+/// colors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMap {
+    /// Use the normal colors.
+    None,
+    /// Color alive cells by their [age](Game::age).
+    Age {
+        /// The color of newborn cells.
+        young: Rgb,
+        /// The color of cells which are at least `max` generations old.
+        old: Rgb,
+        /// The age at which cells reach `old`.
+        max: u16,
+    },
+    /// Color every cell which has ever changed by its [heat](Game::heat).
+    Heat {
+        /// The color of cells which have changed once.
+        cold: Rgb,
+        /// The color of cells which have changed at least `max` times.
+        hot: Rgb,
+        /// The heat at which cells reach `hot`.
+        max: u16,
+    },
+}
+
+impl Default for ColorMap {
+    #[inline]
+    fn default() -> Self {
+        ColorMap::None
+    }
+}
+
+/// How to render a [`Game`] into an [`Image`].
+///
+/// # Examples
+/// ```
+/// # use cgol::image::ImageOptions;
+/// let options = ImageOptions {
+///     cell_size: 4,
+///     grid: Some([128, 128, 128]),
+///     ..ImageOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageOptions {
+    /// The width and height of each cell, in pixels. Defaults to `1`.
+    pub cell_size: usize,
+    /// The color of alive cells. Defaults to black.
+    pub alive: Rgb,
+    /// The color of dead cells. Defaults to white.
+    pub dead: Rgb,
+    /// The color of 1 pixel wide lines around each cell, or `None` to leave them out. Defaults to
+    /// `None`.
+    pub grid: Option<Rgb>,
+    /// How to color cells by their activity. Defaults to [`ColorMap::None`].
+    pub color_map: ColorMap,
+    /// The cells to render, or `None` to render all of them. Parts outside of the game are left
+    /// out. Defaults to `None`.
+    pub crop: Option<Rect>,
+    /// How far to zoom out, where each square of `cell_size` pixels covers `2^zoom_out` by
+    /// `2^zoom_out` cells, blending from `dead` to `alive` by how many of them are alive. The color
+    /// map is ignored when zoomed out. Defaults to `0`.
+    pub zoom_out: u32,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self {
+            cell_size: 1,
+            alive: [0, 0, 0],
+            dead: [255, 255, 255],
+            grid: None,
+            color_map: ColorMap::None,
+            crop: None,
+            zoom_out: 0,
+        }
+    }
+}
+
+impl ImageOptions {
+    /// Gets the width and height in pixels of the image [`Game::render`] makes with these
+    /// options, returning `None` if they overflow.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, image::ImageOptions};
+    /// let game = Game::new(10, 5);
+    /// let options = ImageOptions {
+    ///     cell_size: 3,
+    ///     grid: Some([128; 3]),
+    ///     ..ImageOptions::default()
+    /// };
+    /// assert_eq!(options.image_size(&game), Some((41, 21)));
+    ///
+    /// let options = ImageOptions {
+    ///     cell_size: usize::MAX,
+    ///     ..ImageOptions::default()
+    /// };
+    /// assert_eq!(options.image_size(&game), None);
+    /// ```
+    pub fn image_size(&self, game: &Game) -> Option<(usize, usize)> {
+        let all = Rect::new(0, 0, game.width(), game.height());
+        let view = game.viewport(self.crop.unwrap_or(all), Zoom::Out(self.zoom_out));
+
+        let line = self.grid.is_some() as usize;
+        let pitch = self.cell_size.checked_add(line)?;
+        let size = |cells: usize| cells.checked_mul(pitch)?.checked_add(line);
+
+        Some((size(view.width())?, size(view.height())?))
+    }
+}
+
+/// An RGB image with 8 bits per channel.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    /// Each pixel's red, green and blue, in row-major order.
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Creates an image where every pixel is one color.
+    ///
+    /// # Panics
+    /// Panics if `width * height * 3` overflows.
+    pub fn new(width: usize, height: usize, color: Rgb) -> Image {
+        let area = width
+            .checked_mul(height)
+            .and_then(|area| area.checked_mul(3))
+            .expect("image size overflow");
+
+        let mut pixels = Vec::with_capacity(area);
+        for _ in 0..width * height {
+            pixels.extend_from_slice(&color);
+        }
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    /// Gets this image's width.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Gets this image's height.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Gets every pixel's red, green and blue, in row-major order.
+    #[inline]
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Gets a specific pixel, returning `None` if out of bounds.
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        if x < self.width && y < self.height {
+            let i = (y * self.width + x) * 3;
+            Some([self.pixels[i], self.pixels[i + 1], self.pixels[i + 2]])
+        } else {
+            None
+        }
+    }
+
+    /// Gets each pixel's brightness, from `0` (black) to `255` (white).
+    fn luma(&self) -> impl Iterator<Item = u8> + '_ {
+        self.pixels.chunks_exact(3).map(|p| {
+            // BT.601, which is what most tools use for grayscale
+            ((p[0] as u32 * 299 + p[1] as u32 * 587 + p[2] as u32 * 114) / 1000) as u8
+        })
+    }
+
+    /// Writes this image as a binary PBM (`P4`). Pixels darker than 50% gray are black, and the
+    /// rest are white.
+    pub fn to_pbm(&self) -> Vec<u8> {
+        let mut out = format!("P4\n{} {}\n", self.width, self.height).into_bytes();
+        out.reserve((self.width / 8 + 1) * self.height);
+
+        let luma: Vec<u8> = self.luma().collect();
+        for row in luma.chunks_exact(self.width.max(1)).take(self.height) {
+            for chunk in row.chunks(8) {
+                let byte = chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0, |byte, (i, &l)| byte | ((l < 128) as u8) << (7 - i));
+                out.push(byte);
+            }
+        }
+
+        out
+    }
+
+    /// Writes this image as a binary PGM (`P5`), converting each pixel to grayscale.
+    pub fn to_pgm(&self) -> Vec<u8> {
+        let mut out = format!("P5\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend(self.luma());
+        out
+    }
+
+    /// Writes this image as a binary PPM (`P6`).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        out.extend_from_slice(&self.pixels);
+        out
+    }
+
+    /// Writes this image as a PNG.
+    ///
+    /// # Errors
+    /// Returns an error if writing fails, or if the image is empty or too big for a PNG.
+    #[cfg(feature = "png")]
+    pub fn write_png<W: std::io::Write>(&self, writer: W) -> std::io::Result<()> {
+        use core::convert::TryFrom;
+        use std::io::{Error, ErrorKind};
+
+        let size = |n| match u32::try_from(n) {
+            Ok(n) if n != 0 => Ok(n),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "png images must be between 1 and 2^32 - 1 pixels wide and tall",
+            )),
+        };
+
+        let mut encoder = png::Encoder::new(writer, size(self.width)?, size(self.height)?);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header()?.write_image_data(&self.pixels)?;
+        Ok(())
+    }
+
+    /// Writes this image as a PNG into bytes.
+    ///
+    /// # Panics
+    /// Panics if the image is empty or too big for a PNG.
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        self.write_png(&mut out)
+            .expect("image can't be written as a png");
+        out
+    }
+}
+
+impl Game {
+    /// Renders this game into an image.
+    ///
+    /// Each cell is a square of `cell_size` pixels, and with a grid, there is a 1 pixel line
+    /// around every cell. So, the image is `width * cell_size` pixels wide without a grid, and
+    /// `width * (cell_size + 1) + 1` with one, where `width` is the width of the crop window if
+    /// there is one. Zooming out divides `width` by `2^zoom_out`, rounding up. The size can be
+    /// checked first with [`ImageOptions::image_size`].
+    ///
+    /// # Panics
+    /// Panics if the image's size overflows.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*, image::ImageOptions};
+    /// let mut game = Game::new(3, 2);
+    /// game[(1, 0)] = Alive;
+    ///
+    /// let image = game.render(&ImageOptions {
+    ///     cell_size: 2,
+    ///     ..ImageOptions::default()
+    /// });
+    /// assert_eq!((image.width(), image.height()), (6, 4));
+    /// assert_eq!(image.get(2, 1), Some([0, 0, 0]));
+    /// assert_eq!(image.get(4, 1), Some([255, 255, 255]));
+    ///
+    /// assert_eq!(image.to_pbm(), b"P4\n6 4\n\x30\x30\x00\x00");
+    ///
+    /// // 1 of the 4 cells in the first square is alive
+    /// let image = game.render(&ImageOptions {
+    ///     zoom_out: 1,
+    ///     ..ImageOptions::default()
+    /// });
+    /// assert_eq!((image.width(), image.height()), (2, 1));
+    /// assert_eq!(image.get(0, 0), Some([192, 192, 192]));
+    /// assert_eq!(image.get(1, 0), Some([255, 255, 255]));
+    /// ```
+    pub fn render(&self, options: &ImageOptions) -> Image {
+        let all = Rect::new(0, 0, self.width(), self.height());
+        let view = self.viewport(options.crop.unwrap_or(all), Zoom::Out(options.zoom_out));
+        let crop = view.rect();
+        let (cols, rows) = (view.width(), view.height());
+
+        // how much of each square is alive, when zoomed out
+        let density = if options.zoom_out > 0 {
+            Some(view.density())
+        } else {
+            None
+        };
+
+        let size = options.cell_size;
+        let line = options.grid.is_some() as usize;
+        let pitch = size + line;
+        let (width, height) = options.image_size(self).expect("image size overflow");
+
+        let mut image = Image::new(width, height, options.grid.unwrap_or(options.dead));
+        let row_len = width * 3;
+
+        // one row of pixels, which is copied for every pixel row the cells cover
+        let mut pixels = vec![0; row_len];
+        if let Some(grid) = options.grid {
+            for pixel in pixels.chunks_exact_mut(3) {
+                pixel.copy_from_slice(&grid);
+            }
+        }
+
+        for row in 0..rows {
+            for col in 0..cols {
+                let color = match &density {
+                    Some(density) => {
+                        let value = (density[row * cols + col] * 255.0) as u16;
+                        gradient(options.dead, options.alive, value, 255)
+                    }
+                    None => self.cell_color(crop.y + row, crop.x + col, options),
+                };
+                let start = (col * pitch + line) * 3;
+                for pixel in pixels[start..start + size * 3].chunks_exact_mut(3) {
+                    pixel.copy_from_slice(&color);
+                }
+            }
+
+            let top = row * pitch + line;
+            for y in top..top + size {
+                image.pixels[y * row_len..(y + 1) * row_len].copy_from_slice(&pixels);
+            }
+        }
+
+        image
+    }
+
+    /// Picks the color of a specific cell.
+    fn cell_color(&self, row: usize, col: usize, options: &ImageOptions) -> Rgb {
+        let alive = self.get(row, col) == Some(Cell::Alive);
+        let plain = if alive { options.alive } else { options.dead };
+
+        match options.color_map {
+            ColorMap::None => plain,
+            ColorMap::Age { young, old, max } => match self.age(row, col) {
+                Some(age) if alive => {
+                    gradient(young, old, age.saturating_sub(1), max.saturating_sub(1))
+                }
+                _ => plain,
+            },
+            ColorMap::Heat { cold, hot, max } => match self.heat(row, col) {
+                Some(heat) if heat > 0 => gradient(cold, hot, heat - 1, max.saturating_sub(1)),
+                _ => plain,
+            },
+        }
+    }
+}
+
+/// Blends from `from` to `to`, reaching `to` when `value >= max`.
+fn gradient(from: Rgb, to: Rgb, value: u16, max: u16) -> Rgb {
+    if value >= max {
+        return to;
+    }
+
+    let mut color = from;
+    for (c, (&from, &to)) in color.iter_mut().zip(from.iter().zip(&to)) {
+        let (from, to) = (from as i32, to as i32);
+        *c = (from + (to - from) * value as i32 / max as i32) as u8;
+    }
+    color
+}
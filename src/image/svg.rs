@@ -0,0 +1,188 @@
+//! Contains [`SvgOptions`], which is used to export a [`Game`] as an SVG with [`Game::to_svg`].
+
+use super::Rgb;
+use crate::{cell::Cell, Game, Rect};
+use alloc::string::String;
+use core::fmt::{self, Write};
+
+/// How to export a [`Game`] as an SVG.
+///
+/// # Examples
+/// ```
+/// # use cgol::{Rect, image::svg::SvgOptions};
+/// let options = SvgOptions {
+///     dead: None,
+///     grid: Some([192, 192, 192]),
+///     crop: Some(Rect::new(0, 0, 16, 16)),
+///     ..SvgOptions::default()
+/// };
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SvgOptions {
+    /// The width and height each cell is displayed at, in pixels. Defaults to `10`.
+    ///
+    /// This only sets the SVG's `width` and `height`, so it can still be scaled freely.
+    pub cell_size: usize,
+    /// The color of alive cells. Defaults to black.
+    pub alive: Rgb,
+    /// The color of dead cells, or `None` to leave them transparent. Defaults to white.
+    pub dead: Option<Rgb>,
+    /// The color of lines around each cell, or `None` to leave them out. Lines are a tenth of a
+    /// cell wide. Defaults to `None`.
+    pub grid: Option<Rgb>,
+    /// The cells to show, or `None` to show all of them. Parts outside of the game are left out,
+    /// but at least one cell must be inside it. Defaults to `None`.
+    pub crop: Option<Rect>,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 10,
+            alive: [0; 3],
+            dead: Some([255; 3]),
+            grid: None,
+            crop: None,
+        }
+    }
+}
+
+/// Writes a color as `#rrggbb`.
+struct Hex(Rgb);
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+impl Game {
+    /// Exports this game as a standalone SVG, which can be embedded in HTML or converted for
+    /// LaTeX.
+    ///
+    /// Each unit is one cell, with the `viewBox` set to the crop window, so the coordinates of
+    /// cells are the same with or without a crop. Each run of alive cells in a row is a single
+    /// `<rect>`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::{Game, Cell::*, image::svg::SvgOptions};
+    /// let mut game = Game::new(4, 2);
+    /// game[(1, 0)] = Alive;
+    /// game[(2, 0)] = Alive;
+    ///
+    /// let svg = game.to_svg(&SvgOptions {
+    ///     dead: None,
+    ///     ..SvgOptions::default()
+    /// });
+    /// assert_eq!(
+    ///     svg,
+    ///     r##"<svg xmlns="http://www.w3.org/2000/svg" width="40" height="20" viewBox="0 0 4 2" shape-rendering="crispEdges">
+    /// <g fill="#000000">
+    /// <rect x="1" y="0" width="2" height="1"/>
+    /// </g>
+    /// </svg>
+    /// "##
+    /// );
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if the crop window is completely outside of this game, since an SVG can't be empty,
+    /// or if the size in pixels overflows.
+    ///
+    /// ```should_panic
+    /// # use cgol::{Game, Rect, image::svg::SvgOptions};
+    /// let game = Game::new(4, 2);
+    /// game.to_svg(&SvgOptions {
+    ///     crop: Some(Rect::new(5, 0, 2, 2)),
+    ///     ..SvgOptions::default()
+    /// });
+    /// ```
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let mut svg = String::new();
+        self.write_svg(&mut svg, options)
+            .expect("writing to a string can't fail");
+        svg
+    }
+
+    fn write_svg(&self, svg: &mut String, options: &SvgOptions) -> fmt::Result {
+        let all = Rect::new(0, 0, self.width(), self.height());
+        let crop = options
+            .crop
+            .unwrap_or(all)
+            .clamp(self.width(), self.height());
+        assert!(!crop.is_empty(), "svg crop is outside of the game");
+
+        let (left, top, right, bottom) =
+            (crop.x, crop.y, crop.x + crop.width, crop.y + crop.height);
+        let size = |cells: usize| {
+            cells
+                .checked_mul(options.cell_size)
+                .expect("svg size overflow")
+        };
+
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}" shape-rendering="crispEdges">"#,
+            size(crop.width),
+            size(crop.height),
+            left,
+            top,
+            crop.width,
+            crop.height,
+        )?;
+
+        if let Some(dead) = options.dead {
+            writeln!(
+                svg,
+                r#"<rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+                left,
+                top,
+                crop.width,
+                crop.height,
+                Hex(dead),
+            )?;
+        }
+
+        writeln!(svg, r#"<g fill="{}">"#, Hex(options.alive))?;
+        for row in top..bottom {
+            let mut cells = self.row(row).enumerate().take(right).skip(left).peekable();
+
+            // one rect for each run of alive cells
+            while let Some((start, cell)) = cells.next() {
+                if cell == Cell::Dead {
+                    continue;
+                }
+
+                let mut end = start + 1;
+                while cells.next_if(|&(_, cell)| cell == Cell::Alive).is_some() {
+                    end += 1;
+                }
+
+                writeln!(
+                    svg,
+                    r#"<rect x="{}" y="{}" width="{}" height="1"/>"#,
+                    start,
+                    row,
+                    end - start,
+                )?;
+            }
+        }
+        svg.push_str("</g>\n");
+
+        if let Some(grid) = options.grid {
+            svg.push_str(r#"<path d=""#);
+            for col in left..=right {
+                write!(svg, "M{} {}V{}", col, top, bottom)?;
+            }
+            for row in top..=bottom {
+                write!(svg, "M{} {}H{}", left, row, right)?;
+            }
+            writeln!(svg, r#"" stroke="{}" stroke-width="0.1"/>"#, Hex(grid))?;
+        }
+
+        svg.push_str("</svg>\n");
+        Ok(())
+    }
+}
@@ -1,16 +1,73 @@
 //! [![Tests](https://img.shields.io/github/workflow/status/Purpzie/cgol/CI)](https://github.com/Purpzie/cgol/actions/workflows/ci.yml)
 //!
 //! This crate is a work in progress.
+//!
+//! ## Features
+//! - `alloc` (default): Everything which needs an allocator, which is [`Game`] and the other
+//!   resizable automata, images and text formats. Without it, only [`StaticGame`](static_game::StaticGame),
+//!   [`Cell`], [`Rule`] and [`Rect`] are left.
+//! - `std`: Saving and loading [snapshots](Game::save_to) with [`std::io`].
+//! - `png`: Writing [images](image::Image::to_png) as PNG. Enables `std`.
+//! - `gif`: Recording games as animated GIFs with [`image::gif`]. Enables `std`.
+//! - `use-rand`: Randomly filling games, and randomized ticks.
+//! - `cli`: Builds the `cgol` binary, which has a full screen terminal viewer, and commands for
+//!   running, converting, rendering and getting statistics about patterns in scripts. Enables
+//!   `std`, `use-rand`, `png` and `gif`.
+//! - `serde`: Implements `Serialize` and `Deserialize` for [`Cell`] (as a bool), [`Rule`] (as a
+//!   string like `"B3/S23"`) and [`Game`]. A game is a struct with its `width`, `height`, `rule`,
+//!   `generation`, whether it is `inverted`, and its stored `cells`, which are packed like
+//!   [snapshots](Game::to_snapshot) and written as bytes, or as base64 in human-readable formats.
+//!
+//! ```
+//! # #[cfg(feature = "serde")] {
+//! # use cgol::{Game, Cell::*};
+//! let mut game = Game::new(4, 4);
+//! game[(1, 0)] = Alive;
+//!
+//! let json = serde_json::to_string(&game).unwrap();
+//! assert_eq!(
+//!     json,
+//!     r#"{"width":4,"height":4,"rule":"B3/S23","generation":0,"inverted":false,"cells":"AgA="}"#
+//! );
+//! assert_eq!(serde_json::from_str::<Game>(&json).unwrap(), game);
+//! # }
+//! ```
 
 #![no_std]
 #![doc(html_logo_url = "https://www.rust-lang.org/logos/rust-logo-128x128-blk.png")]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
+#[cfg(feature = "alloc")]
+pub mod automaton;
 mod cell;
+#[cfg(feature = "alloc")]
+pub mod colored;
+#[cfg(feature = "alloc")]
+pub mod elementary;
+#[cfg(feature = "alloc")]
 pub mod game;
+#[cfg(feature = "alloc")]
+pub mod image;
+mod panic;
+mod rect;
+#[cfg(feature = "alloc")]
+mod rle;
+pub mod rule;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod static_game;
 
+#[doc(hidden)]
+pub use cell::pattern as __pattern;
 pub use cell::Cell;
+#[cfg(feature = "alloc")]
 #[doc(inline)]
 pub use game::Game;
+pub use rect::Rect;
+#[doc(inline)]
+pub use rule::Rule;
 pub use Cell::{Alive, Dead};
@@ -0,0 +1,38 @@
+//! Contains panics shared by every kind of game. They are here to reduce code size as much as
+//! possible.
+
+#[cfg(feature = "alloc")]
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn width_is_zero() {
+    panic!("width must not be 0");
+}
+
+#[cfg(feature = "alloc")]
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn height_is_zero() {
+    panic!("height must not be 0");
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn width(val: usize, width: usize) {
+    panic!(
+        "index out of bounds: {} is {} but the index is {}",
+        "width", width, val
+    );
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn height(val: usize, height: usize) {
+    panic!(
+        "index out of bounds: {} is {} but the index is {}",
+        "height", height, val
+    );
+}
@@ -0,0 +1,62 @@
+//! Contains the [`Rect`] struct.
+
+/// A rectangle of cells, such as a crop window.
+///
+/// Like indexing a [`Game`](crate::Game), `x` is the column and `y` is the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    /// The leftmost column.
+    pub x: usize,
+    /// The top row.
+    pub y: usize,
+    /// How many columns it covers.
+    pub width: usize,
+    /// How many rows it covers.
+    pub height: usize,
+}
+
+impl Rect {
+    /// Creates a new rectangle.
+    #[inline]
+    pub const fn new(x: usize, y: usize, width: usize, height: usize) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Returns `true` if it covers no cells.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Returns `true` if it covers a specific cell.
+    #[inline]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x >= self.x && y >= self.y && x - self.x < self.width && y - self.y < self.height
+    }
+
+    /// Gets the part of this rectangle which is inside a board of a specific size.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::Rect;
+    /// let rect = Rect::new(5, 2, 10, 10);
+    /// assert_eq!(rect.clamp(8, 8), Rect::new(5, 2, 3, 6));
+    /// assert!(rect.clamp(4, 4).is_empty());
+    /// ```
+    pub fn clamp(&self, width: usize, height: usize) -> Rect {
+        let x = self.x.min(width);
+        let y = self.y.min(height);
+
+        Rect {
+            x,
+            y,
+            width: self.width.min(width - x),
+            height: self.height.min(height - y),
+        }
+    }
+}
@@ -0,0 +1,246 @@
+//! Contains helpers for reading and writing patterns in the run length encoded (RLE) format used
+//! by Golly.
+
+use crate::game::ParseGameError;
+use alloc::{format, string::String, vec::Vec};
+use core::{convert::TryFrom, fmt::Write};
+
+/// The longest a line is allowed to be, not counting the header.
+const LINE_LEN: usize = 70;
+/// The most cells a header can ask for, so that a pattern can't make us allocate an absurd
+/// amount of memory just by claiming to be huge.
+const MAX_AREA: usize = 1 << 28;
+
+/// Writes a pattern as RLE.
+///
+/// `state` gets the state of a cell at `(row, col)`. With `multistate`, states are written as
+/// `.`, `A`, `B` and so on, and otherwise they are written as `b` (dead) and `o` (alive).
+pub(crate) fn encode(
+    width: usize,
+    height: usize,
+    rule: &str,
+    multistate: bool,
+    state: impl Fn(usize, usize) -> u8,
+) -> String {
+    let mut writer = Writer {
+        out: format!("x = {}, y = {}, rule = {}\n", width, height, rule),
+        line: 0,
+        multistate,
+    };
+
+    // the row the last run was written in
+    let mut last_row = 0;
+
+    for row in 0..height {
+        // trailing dead cells in a row are left out
+        let end = match (0..width).rev().find(|&col| state(row, col) != 0) {
+            Some(col) => col + 1,
+            None => continue,
+        };
+
+        if row > last_row {
+            writer.run(row - last_row, Token::EndRow);
+        }
+        last_row = row;
+
+        let mut col = 0;
+        while col < end {
+            let current = state(row, col);
+            let start = col;
+            while col < end && state(row, col) == current {
+                col += 1;
+            }
+
+            writer.run(col - start, Token::State(current));
+        }
+    }
+
+    writer.push("!");
+    writer.out.push('\n');
+    writer.out
+}
+
+enum Token {
+    State(u8),
+    EndRow,
+}
+
+struct Writer {
+    out: String,
+    line: usize,
+    multistate: bool,
+}
+
+impl Writer {
+    fn run(&mut self, len: usize, token: Token) {
+        let mut item = String::new();
+        if len > 1 {
+            write!(item, "{}", len).unwrap();
+        }
+
+        match token {
+            Token::EndRow => item.push('$'),
+            Token::State(state) if !self.multistate => {
+                item.push(if state == 0 { 'b' } else { 'o' })
+            }
+            Token::State(0) => item.push('.'),
+            Token::State(state @ 1..=24) => item.push((b'A' + state - 1) as char),
+            Token::State(state) => {
+                // states above 24 get a prefix, so 25 is `pA`, 49 is `qA` and so on
+                item.push((b'p' + (state - 25) / 24) as char);
+                item.push((b'A' + (state - 25) % 24) as char);
+            }
+        }
+
+        self.push(&item);
+    }
+
+    fn push(&mut self, item: &str) {
+        if self.line + item.len() > LINE_LEN {
+            self.out.push('\n');
+            self.line = 0;
+        }
+
+        self.out.push_str(item);
+        self.line += item.len();
+    }
+}
+
+/// A pattern read from RLE.
+pub(crate) struct Pattern<'a> {
+    pub width: usize,
+    pub height: usize,
+    /// The rule from the header, if there is one.
+    pub rule: Option<&'a str>,
+    /// Every run of cells which aren't dead, as `(row, col, len, state)`.
+    pub runs: Vec<(usize, usize, usize, u8)>,
+}
+
+/// Reads a pattern from RLE. Lines starting with `#` are comments, and anything after the `!` is
+/// ignored.
+///
+/// States are read like [`encode`] writes them, so `o` is `1`, `A` is `1`, `B` is `2`, and so on.
+pub(crate) fn decode(s: &str) -> Result<Pattern<'_>, ParseGameError> {
+    let mut lines = s
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let (header_line, header) = lines
+        .next()
+        .ok_or_else(|| ParseGameError::new(0, "expected a header"))?;
+    let mut pattern =
+        parse_header(header).map_err(|reason| ParseGameError::new(header_line, reason))?;
+
+    let (mut row, mut col): (usize, usize) = (0, 0);
+    // a run length which is being read, and a prefix like `p` in `pA`
+    let mut count: Option<usize> = None;
+    let mut prefix: Option<u8> = None;
+
+    'lines: for (line, text) in lines {
+        let error = |reason| ParseGameError::new(line, reason);
+
+        for b in text.bytes() {
+            if prefix.is_some() && !matches!(b, b'A'..=b'X') {
+                return Err(error("expected a state after its prefix"));
+            }
+
+            let state = match b {
+                b'0'..=b'9' => {
+                    count = count
+                        .unwrap_or(0)
+                        .checked_mul(10)
+                        .and_then(|n| n.checked_add((b - b'0') as usize));
+                    if count.is_none() {
+                        return Err(error("run is too long"));
+                    }
+                    continue;
+                }
+                b'p'..=b'y' => {
+                    prefix = Some(b);
+                    continue;
+                }
+                b if b.is_ascii_whitespace() => continue,
+                b'!' => break 'lines,
+                b'$' => {
+                    row = row.saturating_add(count.take().unwrap_or(1));
+                    col = 0;
+                    continue;
+                }
+                b'b' | b'.' => 0,
+                b'A'..=b'X' => {
+                    let offset = prefix.take().map_or(0, |p| (p - b'p' + 1) as usize * 24);
+                    u8::try_from(offset + (b - b'A') as usize + 1)
+                        .map_err(|_| error("state is too high"))?
+                }
+                // any other letter is alive, like in Golly
+                b if b.is_ascii_alphabetic() => 1,
+                _ => return Err(error("unexpected character")),
+            };
+
+            let len = count.take().unwrap_or(1);
+            let end = col
+                .checked_add(len)
+                .ok_or_else(|| error("run is too long"))?;
+            if state != 0 {
+                if row >= pattern.height || end > pattern.width {
+                    return Err(error("pattern is bigger than its header says"));
+                }
+                pattern.runs.push((row, col, len, state));
+            }
+            col = end;
+        }
+    }
+
+    Ok(pattern)
+}
+
+/// Parses a header like `x = 3, y = 3, rule = B3/S23`.
+fn parse_header(header: &str) -> Result<Pattern<'_>, &'static str> {
+    // the rule is last, and can contain commas, like `B3/S23:T10,10`
+    let (sizes, rule) = match header.find("rule") {
+        Some(i) => {
+            let rule = header[i + 4..].trim_start();
+            match rule.strip_prefix('=') {
+                Some(rule) => (&header[..i], Some(rule.trim())),
+                None => return Err("expected `=` after `rule`"),
+            }
+        }
+        None => (header, None),
+    };
+
+    let (mut width, mut height) = (None, None);
+    for part in sizes
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        let (key, value) = match part.find('=') {
+            Some(i) => (part[..i].trim(), part[i + 1..].trim()),
+            None => return Err("expected `key = value` in the header"),
+        };
+        let value: usize = value.parse().map_err(|_| "invalid size in the header")?;
+
+        match key {
+            "x" => width = Some(value),
+            "y" => height = Some(value),
+            _ => return Err("unknown key in the header"),
+        }
+    }
+
+    let (width, height) = match (width, height) {
+        (Some(width), Some(height)) => (width, height),
+        _ => return Err("expected `x` and `y` in the header"),
+    };
+
+    match width.checked_mul(height) {
+        Some(area) if area <= MAX_AREA => Ok(Pattern {
+            width,
+            height,
+            rule,
+            runs: Vec::new(),
+        }),
+        _ => Err("pattern is too big"),
+    }
+}
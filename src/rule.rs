@@ -0,0 +1,224 @@
+//! Contains the [`Rule`] struct.
+
+use crate::cell::Cell;
+use core::{fmt, str::FromStr};
+
+/// A life-like rule, written in B/S notation (such as `B3/S23`).
+///
+/// A dead cell is born if its number of alive neighbors is listed after the `B`, and an alive
+/// cell survives if its number of alive neighbors is listed after the `S`. Every other cell dies
+/// or stays dead.
+///
+/// ## B0 rules
+/// Rules containing `B0` cause every dead cell with no alive neighbors to be born, which would
+/// make the whole background flicker. [`Game`](crate::Game) emulates these rules the same way
+/// Golly does:
+/// - Without `S8`, it alternates between the rule and its complement every generation, so the
+///   stored cells are inverted on odd generations.
+/// - With `S8`, it always runs the complement of the rule, so the stored cells are always
+///   inverted.
+///
+/// Either way, the background of the stored cells stays dead.
+///
+/// # Examples
+/// ```
+/// # use cgol::Rule;
+/// let highlife: Rule = "B36/S23".parse().unwrap();
+///
+/// assert!(highlife.born(6));
+/// assert!(!highlife.survives(6));
+/// assert_eq!(highlife.to_string(), "B36/S23");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rule {
+    birth: u16,
+    survival: u16,
+}
+
+impl Rule {
+    /// Conway's Game of Life, `B3/S23`.
+    pub const LIFE: Rule = Rule {
+        birth: 1 << 3,
+        survival: 1 << 2 | 1 << 3,
+    };
+
+    /// Creates a rule from lists of neighbor counts.
+    ///
+    /// # Panics
+    /// Panics if any neighbor count is greater than `8`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::Rule;
+    /// assert_eq!(Rule::new(&[3], &[2, 3]), Rule::LIFE);
+    /// ```
+    pub fn new(birth: &[u8], survival: &[u8]) -> Rule {
+        fn mask(counts: &[u8]) -> u16 {
+            counts.iter().fold(0, |mask, &n| {
+                assert!(n <= 8, "neighbor count must not be greater than 8");
+                mask | 1 << n
+            })
+        }
+
+        Rule {
+            birth: mask(birth),
+            survival: mask(survival),
+        }
+    }
+
+    /// Returns `true` if a dead cell with `neighbors` alive neighbors is born.
+    #[inline]
+    pub fn born(&self, neighbors: u8) -> bool {
+        neighbors <= 8 && self.birth & 1 << neighbors != 0
+    }
+
+    /// Returns `true` if an alive cell with `neighbors` alive neighbors survives.
+    #[inline]
+    pub fn survives(&self, neighbors: u8) -> bool {
+        neighbors <= 8 && self.survival & 1 << neighbors != 0
+    }
+
+    /// Returns `true` if this rule contains `B0`.
+    ///
+    /// See the [type-level documentation](Rule#b0-rules) for how these are handled.
+    ///
+    /// # Examples
+    /// ```
+    /// # use cgol::Rule;
+    /// assert!(!Rule::LIFE.is_b0());
+    /// assert!("B0123478/S34678".parse::<Rule>().unwrap().is_b0());
+    /// ```
+    #[inline]
+    pub fn is_b0(&self) -> bool {
+        self.born(0)
+    }
+
+    /// Decides whether the stored cells will be inverted after the next tick, given whether they
+    /// are inverted now.
+    pub(crate) fn next_inverted(&self, inverted: bool) -> bool {
+        if !self.is_b0() {
+            false
+        } else if self.survives(8) {
+            true
+        } else {
+            !inverted
+        }
+    }
+
+    /// Builds a lookup table indexed by `[stored cell][stored alive neighbors]`, which steps the
+    /// stored cells from one phase to the next.
+    pub(crate) fn table(&self, inverted: bool, next_inverted: bool) -> [[Cell; 9]; 2] {
+        let mut table = [[Cell::Dead; 9]; 2];
+
+        for (stored, row) in table.iter_mut().enumerate() {
+            let alive = (stored == 1) ^ inverted;
+
+            for (n, cell) in row.iter_mut().enumerate() {
+                let n = if inverted { 8 - n as u8 } else { n as u8 };
+                let next = if alive {
+                    self.survives(n)
+                } else {
+                    self.born(n)
+                };
+
+                *cell = Cell::from(next ^ next_inverted);
+            }
+        }
+
+        table
+    }
+
+    /// Gets the birth and survival bitmasks, where bit `n` is set if `n` neighbors are listed.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn masks(&self) -> (u16, u16) {
+        (self.birth, self.survival)
+    }
+
+    /// Creates a rule from bitmasks, returning `None` if a bit above `8` is set.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_masks(birth: u16, survival: u16) -> Option<Rule> {
+        if (birth | survival) >> 9 == 0 {
+            Some(Rule { birth, survival })
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for Rule {
+    #[inline]
+    fn default() -> Self {
+        Rule::LIFE
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("B")?;
+        for n in (0..=8).filter(|&n| self.born(n)) {
+            write!(f, "{}", n)?;
+        }
+
+        f.write_str("/S")?;
+        for n in (0..=8).filter(|&n| self.survives(n)) {
+            write!(f, "{}", n)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// An error which can be returned when parsing a [`Rule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuleError(());
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid rule, expected something like `B3/S23` or `23/3`")
+    }
+}
+
+/// Parses `B3/S23`, `B3S23`, `S23/B3`, or `23/3` (survival first). Letters are case-insensitive.
+impl FromStr for Rule {
+    type Err = ParseRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn mask(digits: &str) -> Result<u16, ParseRuleError> {
+            digits.bytes().try_fold(0, |mask, b| match b {
+                b'0'..=b'8' => Ok(mask | 1 << (b - b'0')),
+                _ => Err(ParseRuleError(())),
+            })
+        }
+
+        let s = s.trim();
+        let upper = |b: u8| b.to_ascii_uppercase();
+        let (first, second) = match s.find('/') {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => {
+                let is_letter = |c: char| c == 'S' || c == 's' || c == 'B' || c == 'b';
+                match s.get(1..).and_then(|rest| rest.find(is_letter)) {
+                    Some(i) => (&s[..i + 1], &s[i + 1..]),
+                    None => return Err(ParseRuleError(())),
+                }
+            }
+        };
+
+        let (birth, survival) = match (
+            first.bytes().next().map(upper),
+            second.bytes().next().map(upper),
+        ) {
+            (Some(b'B'), Some(b'S')) => (&first[1..], &second[1..]),
+            (Some(b'S'), Some(b'B')) => (&second[1..], &first[1..]),
+            // plain "survival/birth", as used by older programs
+            (Some(b'B'), _) | (Some(b'S'), _) | (_, Some(b'B')) | (_, Some(b'S')) => {
+                return Err(ParseRuleError(()));
+            }
+            _ => (second, first),
+        };
+
+        Ok(Rule {
+            birth: mask(birth)?,
+            survival: mask(survival)?,
+        })
+    }
+}
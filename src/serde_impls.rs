@@ -0,0 +1,205 @@
+//! Contains the implementations of `Serialize` and `Deserialize`, behind the `serde` feature.
+
+use crate::{Cell, Game, Rule};
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+use serde::{
+    de::{self, Deserializer, Visitor},
+    ser::Serializer,
+    Deserialize, Serialize,
+};
+
+impl Serialize for Cell {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bool(bool::from(*self))
+    }
+}
+
+impl<'de> Deserialize<'de> for Cell {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        bool::deserialize(deserializer).map(Cell::from)
+    }
+}
+
+impl Serialize for Rule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Rule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+/// How a [`Game`] is laid out when serialized.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Game")]
+struct Repr {
+    width: usize,
+    height: usize,
+    rule: Rule,
+    generation: u64,
+    inverted: bool,
+    cells: Packed,
+}
+
+impl Serialize for Game {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Repr {
+            width: self.width(),
+            height: self.height(),
+            rule: self.rule(),
+            generation: self.generation(),
+            inverted: self.is_inverted(),
+            cells: Packed(self.packed_cells().collect()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Game {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = Repr::deserialize(deserializer)?;
+
+        if repr.width == 0 || repr.height == 0 {
+            return Err(de::Error::custom("width and height must not be 0"));
+        }
+
+        let area = match repr.width.checked_mul(repr.height) {
+            Some(area) if area <= isize::MAX as usize => area,
+            _ => return Err(de::Error::custom("width * height is too big")),
+        };
+
+        let expected = (area - 1) / 8 + 1;
+        if repr.cells.0.len() != expected {
+            return Err(de::Error::invalid_length(
+                repr.cells.0.len(),
+                &"ceil(width * height / 8) bytes of cells",
+            ));
+        }
+
+        let mut game = Game::with_rule(repr.width, repr.height, repr.rule);
+        game.unpack_cells(&repr.cells.0, repr.inverted);
+        game.set_generation(repr.generation);
+        Ok(game)
+    }
+}
+
+/// Bit-packed cells, which are base64 in human-readable formats and bytes otherwise.
+struct Packed(Vec<u8>);
+
+impl Serialize for Packed {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&base64::encode(&self.0))
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Packed {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PackedVisitor;
+
+        impl<'de> Visitor<'de> for PackedVisitor {
+            type Value = Packed;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("bit-packed cells as bytes or a base64 string")
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<Packed, E> {
+                base64::decode(s)
+                    .map(Packed)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(s), &self))
+            }
+
+            fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Packed, E> {
+                Ok(Packed(bytes.to_vec()))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Packed, E> {
+                Ok(Packed(bytes))
+            }
+
+            // some formats write bytes as a sequence of numbers. The length comes from the
+            // input, so only part of it is reserved up front
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Packed, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(Packed(bytes))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(PackedVisitor)
+        } else {
+            deserializer.deserialize_byte_buf(PackedVisitor)
+        }
+    }
+}
+
+/// Standard base64 with padding, which is all that's needed for the cells.
+mod base64 {
+    use alloc::{string::String, vec::Vec};
+
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    pub(super) fn encode(bytes: &[u8]) -> String {
+        let mut out = String::with_capacity((bytes.len() / 3 + 1) * 4);
+
+        for chunk in bytes.chunks(3) {
+            let n = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+                } else {
+                    out.push('=');
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Returns `None` if the string isn't valid base64.
+    pub(super) fn decode(s: &str) -> Option<Vec<u8>> {
+        let s = s.as_bytes();
+        if !s.chunks_exact(4).remainder().is_empty() {
+            return None;
+        }
+
+        let mut out = Vec::with_capacity(s.len() / 4 * 3);
+        for (index, chunk) in s.chunks(4).enumerate() {
+            let last = index == s.len() / 4 - 1;
+            let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+            if padding > 2 || (padding > 0 && !last) {
+                return None;
+            }
+
+            let mut n = 0u32;
+            for &c in &chunk[..4 - padding] {
+                let digit = ALPHABET.iter().position(|&a| a == c)?;
+                n = n << 6 | digit as u32;
+            }
+            n <<= 6 * padding as u32;
+
+            for i in 0..3 - padding {
+                out.push((n >> (16 - 8 * i)) as u8);
+            }
+        }
+
+        Some(out)
+    }
+}
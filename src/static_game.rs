@@ -0,0 +1,666 @@
+//! Contains the [`StaticGame`] struct, which runs life-like rules without allocating.
+
+#[cfg(feature = "alloc")]
+use crate::Game;
+use crate::{cell::Cell, panic, Rule};
+#[cfg(feature = "alloc")]
+use core::convert::TryFrom;
+use core::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// An instance of a life-like rule with a size known at compile time, which never allocates.
+///
+/// It works like [`Game`], including wrapping edges and the emulation of
+/// [B0 rules](Rule#b0-rules), but each row is bit-packed into `WORDS` [`u64`]s and 64 cells are
+/// stepped at once. `WORDS` must be `(W + 63) / 64`, which is `1` by default, so it only has to be
+/// given for games wider than 64 cells. `W` and `H` must not be `0`. Otherwise, it fails to
+/// compile.
+///
+/// It doesn't need the `alloc` feature, so it can be used without an allocator at all.
+///
+/// Like with [`Game`], every method works with the logical state, and methods which
+/// give mutable access to cells first flip the stored cells back if they're
+/// [inverted](StaticGame::is_inverted). Since cells are bits, mutable access copies the row or
+/// column being borrowed into a small buffer, which is packed back in before the cells are used
+/// for anything else.
+///
+/// # Examples
+/// ```
+/// # use cgol::{static_game::StaticGame, Cell::*};
+/// let mut game = StaticGame::<5, 5>::new();
+///
+/// // a blinker, where indexing is `(col, row)` like with `Game`
+/// game.set(1, 2, Alive);
+/// game[(2, 2)] = Alive;
+/// game[(2, 3)] = Alive;
+///
+/// game.tick();
+/// assert_eq!(game.row(2).collect::<Vec<_>>(), &[Dead, Alive, Alive, Alive, Dead]);
+/// assert_eq!(game[(2, 1)], Dead);
+///
+/// // wider games need more words per row
+/// let mut wide = StaticGame::<128, 64, 2>::new();
+/// for cell in wide.row_mut(10).skip(100).take(3) {
+///     *cell = Alive;
+/// }
+/// wide.tick();
+/// assert_eq!(wide.col(101).filter(|&cell| cell == Alive).count(), 3);
+/// ```
+///
+/// ```compile_fail
+/// # use cgol::static_game::StaticGame;
+/// // 65 cells don't fit in one word
+/// let game = StaticGame::<65, 5>::new();
+/// ```
+#[derive(Clone, Copy)]
+pub struct StaticGame<const W: usize, const H: usize, const WORDS: usize = 1> {
+    /// Bit `col % 64` of word `col / 64` in `rows[row]` is the stored cell at `(row, col)`. Bits
+    /// past the width are always `0`.
+    rows: [[u64; WORDS]; H],
+    rule: Rule,
+    inverted: bool,
+    /// The row or column which is borrowed mutably, if any. Its cells are in `row` or `col`
+    /// instead of `rows`, and `rows` isn't inverted.
+    borrowed: Borrowed,
+    row: [Cell; W],
+    col: [Cell; H],
+}
+
+/// Which cells of a [`StaticGame`] are unpacked to be borrowed mutably.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Borrowed {
+    None,
+    Row(usize),
+    Col(usize),
+}
+
+impl<const W: usize, const H: usize, const WORDS: usize> StaticGame<W, H, WORDS> {
+    /// Fails to compile if the size isn't supported.
+    const VALID_SIZE: () = assert!(
+        W >= 1 && H >= 1 && WORDS == (W - 1) / 64 + 1,
+        "StaticGame must be at least 1 cell wide and 1 cell tall, with (W + 63) / 64 words per row"
+    );
+
+    /// The bits of the last word in a row which are inside the width.
+    const LAST_MASK: u64 = u64::MAX >> ((64 - W % 64) % 64);
+
+    /// Creates a new instance of Conway's Game of Life, where every cell is dead.
+    pub const fn new() -> Self {
+        Self::with_rule(Rule::LIFE)
+    }
+
+    /// Creates a new instance of a game running a specific rule, where every cell is dead.
+    pub const fn with_rule(rule: Rule) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_SIZE;
+
+        Self {
+            rows: [[0; WORDS]; H],
+            rule,
+            inverted: false,
+            borrowed: Borrowed::None,
+            row: [Cell::Dead; W],
+            col: [Cell::Dead; H],
+        }
+    }
+
+    /// Gets the rule this game is running.
+    #[inline]
+    pub fn rule(&self) -> Rule {
+        self.rule
+    }
+
+    /// Changes the rule this game is running. The cells are left untouched.
+    #[inline]
+    pub fn set_rule(&mut self, rule: Rule) {
+        self.rule = rule;
+    }
+
+    /// Returns `true` if the stored cells are currently the inverse of the logical state. See
+    /// [`Game::is_inverted`](crate::Game::is_inverted).
+    #[inline]
+    pub fn is_inverted(&self) -> bool {
+        self.inverted
+    }
+
+    /// Gets this game's width.
+    #[inline]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    /// Gets this game's height.
+    #[inline]
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// Gets this game's area (`width * height`).
+    #[inline]
+    pub const fn area(&self) -> usize {
+        W * H
+    }
+
+    /// Ticks once.
+    pub fn tick(&mut self) {
+        self.release();
+        let next_inverted = self.rule.next_inverted(self.inverted);
+        let table = self.rule.table(self.inverted, next_inverted);
+
+        // masks of which stored neighbor counts give an alive cell, for dead and alive cells
+        let mut born = 0u16;
+        let mut survives = 0u16;
+        for (n, (&dead, &alive)) in table[0].iter().zip(&table[1]).enumerate() {
+            born |= (dead as u16) << n;
+            survives |= (alive as u16) << n;
+        }
+
+        // rows are overwritten in place, so the old rows above and at the top are kept around
+        let first = self.rows[0];
+        let mut up = self.rows[H - 1];
+
+        for row in 0..H {
+            let center = self.rows[row];
+            let down = if row == H - 1 {
+                first
+            } else {
+                self.rows[row + 1]
+            };
+
+            let mut next = [0; WORDS];
+            for (word, next) in next.iter_mut().enumerate() {
+                // each neighbor count is 4 bits, and bit `i` of each word is bit `i` of the count
+                let mut count = [0u64; 4];
+                for line in &[&up, &center, &down] {
+                    Self::add(&mut count, Self::left(line, word));
+                    Self::add(&mut count, Self::right(line, word));
+                }
+                Self::add(&mut count, up[word]);
+                Self::add(&mut count, down[word]);
+
+                for n in 0..9 {
+                    let alive = survives >> n & 1 != 0;
+                    let dead = born >> n & 1 != 0;
+                    if !alive && !dead {
+                        continue;
+                    }
+
+                    let pick = |bit: usize| {
+                        if n >> bit & 1 != 0 {
+                            count[bit]
+                        } else {
+                            !count[bit]
+                        }
+                    };
+                    let equal = pick(0) & pick(1) & pick(2) & pick(3);
+                    let cells = match (alive, dead) {
+                        (true, true) => u64::MAX,
+                        (true, false) => center[word],
+                        _ => !center[word],
+                    };
+                    *next |= equal & cells;
+                }
+            }
+            next[WORDS - 1] &= Self::LAST_MASK;
+
+            up = center;
+            self.rows[row] = next;
+        }
+
+        self.inverted = next_inverted;
+    }
+
+    /// Gets each cell's left neighbor in one word of a row, wrapping around.
+    #[inline]
+    fn left(row: &[u64; WORDS], word: usize) -> u64 {
+        let carry = if word == 0 {
+            row[WORDS - 1] >> ((W - 1) % 64) & 1
+        } else {
+            row[word - 1] >> 63
+        };
+        row[word] << 1 | carry
+    }
+
+    /// Gets each cell's right neighbor in one word of a row, wrapping around.
+    #[inline]
+    fn right(row: &[u64; WORDS], word: usize) -> u64 {
+        let carry = if word == WORDS - 1 {
+            (row[0] & 1) << ((W - 1) % 64)
+        } else {
+            row[word + 1] << 63
+        };
+        row[word] >> 1 | carry
+    }
+
+    /// Adds one bit to every count at once.
+    #[inline]
+    fn add(count: &mut [u64; 4], mut carry: u64) {
+        for bit in count {
+            let next_carry = *bit & carry;
+            *bit ^= carry;
+            carry = next_carry;
+        }
+    }
+
+    /// Gets whether the stored bit for a cell is set.
+    #[inline]
+    fn stored(&self, row: usize, col: usize) -> bool {
+        self.rows[row][col / 64] >> (col % 64) & 1 != 0
+    }
+
+    /// Sets the stored bit for a cell.
+    #[inline]
+    fn store(&mut self, row: usize, col: usize, cell: Cell) {
+        let bit = 1 << (col % 64);
+        match cell {
+            Cell::Alive => self.rows[row][col / 64] |= bit,
+            Cell::Dead => self.rows[row][col / 64] &= !bit,
+        }
+    }
+
+    /// Gets the logical state of a cell, which must be in bounds.
+    #[inline]
+    fn cell(&self, row: usize, col: usize) -> Cell {
+        match self.borrowed {
+            Borrowed::Row(borrowed) if borrowed == row => self.row[col],
+            Borrowed::Col(borrowed) if borrowed == col => self.col[row],
+            _ => Cell::from(self.stored(row, col) ^ self.inverted),
+        }
+    }
+
+    /// Packs the borrowed row or column back into the stored cells. This must be called before
+    /// using the stored cells for anything else.
+    fn release(&mut self) {
+        match self.borrowed {
+            Borrowed::None => {}
+            Borrowed::Row(row) => {
+                for col in 0..W {
+                    self.store(row, col, self.row[col]);
+                }
+            }
+            Borrowed::Col(col) => {
+                for row in 0..H {
+                    self.store(row, col, self.col[row]);
+                }
+            }
+        }
+        self.borrowed = Borrowed::None;
+    }
+
+    /// Unpacks a row so it can be borrowed mutably.
+    fn borrow_row(&mut self, row: usize) -> &mut [Cell; W] {
+        if self.borrowed != Borrowed::Row(row) {
+            self.release();
+            self.uninvert();
+            for col in 0..W {
+                self.row[col] = Cell::from(self.stored(row, col));
+            }
+            self.borrowed = Borrowed::Row(row);
+        }
+
+        &mut self.row
+    }
+
+    /// Unpacks a column so it can be borrowed mutably.
+    fn borrow_col(&mut self, col: usize) -> &mut [Cell; H] {
+        if self.borrowed != Borrowed::Col(col) {
+            self.release();
+            self.uninvert();
+            for row in 0..H {
+                self.col[row] = Cell::from(self.stored(row, col));
+            }
+            self.borrowed = Borrowed::Col(col);
+        }
+
+        &mut self.col
+    }
+
+    /// Flips the stored cells back if they're inverted, so they match the logical state. Nothing
+    /// may be borrowed.
+    fn uninvert(&mut self) {
+        if self.inverted {
+            self.flip();
+            self.inverted = false;
+        }
+    }
+
+    /// Flips every stored cell. Nothing may be borrowed.
+    fn flip(&mut self) {
+        for row in &mut self.rows {
+            for word in row.iter_mut() {
+                *word = !*word;
+            }
+            row[WORDS - 1] &= Self::LAST_MASK;
+        }
+    }
+
+    /// Gets a specific cell, returning `None` if out of bounds.
+    pub fn get(&self, row: usize, col: usize) -> Option<Cell> {
+        if row < H && col < W {
+            Some(self.cell(row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Gets a mutable reference to a specific cell, returning `None` if out of bounds.
+    ///
+    /// If this game is [inverted](StaticGame::is_inverted), the stored cells are flipped back
+    /// first.
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut Cell> {
+        if row < H && col < W {
+            Some(&mut self[(col, row)])
+        } else {
+            None
+        }
+    }
+
+    /// Sets a specific cell.
+    ///
+    /// # Panics
+    /// Panics if `row` or `col` are out of bounds.
+    pub fn set(&mut self, row: usize, col: usize, cell: Cell) {
+        self[(col, row)] = cell;
+    }
+
+    /// Gets an iterator over the cells in a specific row.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn row(&self, row: usize) -> impl Iterator<Item = Cell> + '_ {
+        if row >= H {
+            panic::height(row, H);
+        }
+
+        (0..W).map(move |col| self.cell(row, col))
+    }
+
+    /// Gets a mutable iterator over the cells in a specific row.
+    ///
+    /// If this game is [inverted](StaticGame::is_inverted), the stored cells are flipped back
+    /// first.
+    ///
+    /// # Panics
+    /// Panics if `row` is out of bounds.
+    pub fn row_mut(&mut self, row: usize) -> impl Iterator<Item = &mut Cell> + '_ {
+        if row >= H {
+            panic::height(row, H);
+        }
+
+        self.borrow_row(row).iter_mut()
+    }
+
+    /// Gets an iterator over the cells in a specific column.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn col(&self, col: usize) -> impl Iterator<Item = Cell> + '_ {
+        if col >= W {
+            panic::width(col, W);
+        }
+
+        (0..H).map(move |row| self.cell(row, col))
+    }
+
+    /// Gets a mutable iterator over the cells in a specific column.
+    ///
+    /// If this game is [inverted](StaticGame::is_inverted), the stored cells are flipped back
+    /// first.
+    ///
+    /// # Panics
+    /// Panics if `col` is out of bounds.
+    pub fn col_mut(&mut self, col: usize) -> impl Iterator<Item = &mut Cell> + '_ {
+        if col >= W {
+            panic::width(col, W);
+        }
+
+        self.borrow_col(col).iter_mut()
+    }
+
+    /// Kills all cells.
+    pub fn clear(&mut self) {
+        self.rows = [[0; WORDS]; H];
+        self.inverted = false;
+        self.borrowed = Borrowed::None;
+    }
+
+    /// Counts the alive cells.
+    pub fn population(&self) -> usize {
+        let count = |words: &[u64]| words.iter().map(|w| w.count_ones() as usize).sum::<usize>();
+        let alive = |cells: &[Cell]| cells.iter().filter(|&&c| c == Cell::Alive).count();
+
+        let stored: usize = self.rows.iter().map(|row| count(row)).sum();
+        let stored = match self.borrowed {
+            Borrowed::None => stored,
+            Borrowed::Row(row) => stored - count(&self.rows[row]) + alive(&self.row),
+            Borrowed::Col(col) => {
+                let packed = (0..H).filter(|&row| self.stored(row, col)).count();
+                stored - packed + alive(&self.col)
+            }
+        };
+
+        if self.inverted {
+            W * H - stored
+        } else {
+            stored
+        }
+    }
+
+    /// Returns `true` if all cells are [`Dead`](Cell::Dead).
+    pub fn all_dead(&self) -> bool {
+        self.population() == 0
+    }
+
+    /// Returns `true` if all cells are [`Alive`](Cell::Alive).
+    pub fn all_alive(&self) -> bool {
+        self.population() == W * H
+    }
+
+    /// Inverts all cells.
+    pub fn invert(&mut self) {
+        self.release();
+        self.flip();
+    }
+}
+
+impl<const W: usize, const H: usize, const WORDS: usize> Default for StaticGame<W, H, WORDS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Compares the rule and the logical state of every cell, like [`Game`] does.
+///
+/// # Examples
+/// ```
+/// # use cgol::static_game::StaticGame;
+/// // the empty board is stored inverted on every other generation
+/// let mut a = StaticGame::<4, 4>::with_rule("B0/S".parse().unwrap());
+/// a.tick();
+/// a.tick();
+///
+/// let mut b = StaticGame::<4, 4>::with_rule("B0/S".parse().unwrap());
+/// b.invert();
+/// b.tick();
+///
+/// assert_ne!(a.is_inverted(), b.is_inverted());
+/// assert_eq!(a, b);
+/// ```
+impl<const W: usize, const H: usize, const WORDS: usize> PartialEq for StaticGame<W, H, WORDS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.rule == other.rule && (0..H).all(|row| self.row(row).eq(other.row(row)))
+    }
+}
+
+impl<const W: usize, const H: usize, const WORDS: usize> Eq for StaticGame<W, H, WORDS> {}
+
+/// Shows the size, the rule and the logical state of every cell, like
+/// [`Game`](crate::Game#impl-Debug-for-Game) does.
+///
+/// # Examples
+/// ```
+/// # use cgol::{static_game::StaticGame, Cell::*};
+/// let mut game = StaticGame::<3, 2>::new();
+/// game[(1, 0)] = Alive;
+///
+/// assert_eq!(
+///     format!("{:?}", game),
+///     r#"StaticGame { width: 3, height: 2, rule: B3/S23, cells: [".O.", "..."] }"#
+/// );
+/// ```
+impl<const W: usize, const H: usize, const WORDS: usize> fmt::Debug for StaticGame<W, H, WORDS> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// Shows every row as a list of strings.
+        struct Cells<'a, const W: usize, const H: usize, const WORDS: usize>(
+            &'a StaticGame<W, H, WORDS>,
+        );
+
+        impl<const W: usize, const H: usize, const WORDS: usize> fmt::Debug for Cells<'_, W, H, WORDS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list()
+                    .entries((0..H).map(|row| Row(self.0, row)))
+                    .finish()
+            }
+        }
+
+        /// Shows a row as a string.
+        struct Row<'a, const W: usize, const H: usize, const WORDS: usize>(
+            &'a StaticGame<W, H, WORDS>,
+            usize,
+        );
+
+        impl<const W: usize, const H: usize, const WORDS: usize> fmt::Debug for Row<'_, W, H, WORDS> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("\"")?;
+                for cell in self.0.row(self.1) {
+                    f.write_str(if cell == Cell::Alive { "O" } else { "." })?;
+                }
+                f.write_str("\"")
+            }
+        }
+
+        f.debug_struct("StaticGame")
+            .field("width", &W)
+            .field("height", &H)
+            .field("rule", &format_args!("{}", self.rule))
+            .field("cells", &Cells(self))
+            .finish()
+    }
+}
+
+impl<const W: usize, const H: usize, const WORDS: usize> Index<(usize, usize)>
+    for StaticGame<W, H, WORDS>
+{
+    type Output = Cell;
+
+    fn index(&self, (col, row): (usize, usize)) -> &Cell {
+        if row >= H {
+            panic::height(row, H);
+        } else if col >= W {
+            panic::width(col, W);
+        }
+
+        match self.borrowed {
+            Borrowed::Row(borrowed) if borrowed == row => &self.row[col],
+            Borrowed::Col(borrowed) if borrowed == col => &self.col[row],
+            _ if self.stored(row, col) ^ self.inverted => &Cell::Alive,
+            _ => &Cell::Dead,
+        }
+    }
+}
+
+/// If the game is [inverted](StaticGame::is_inverted), the stored cells are flipped back first.
+impl<const W: usize, const H: usize, const WORDS: usize> IndexMut<(usize, usize)>
+    for StaticGame<W, H, WORDS>
+{
+    fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut Cell {
+        if row >= H {
+            panic::height(row, H);
+        } else if col >= W {
+            panic::width(col, W);
+        }
+
+        &mut self.borrow_row(row)[col]
+    }
+}
+
+/// Copies the logical state of every cell, so the new game is never
+/// [inverted](Game::is_inverted).
+#[cfg(feature = "alloc")]
+impl<const W: usize, const H: usize, const WORDS: usize> From<&StaticGame<W, H, WORDS>> for Game {
+    fn from(game: &StaticGame<W, H, WORDS>) -> Game {
+        let mut new = Game::with_rule(W, H, game.rule);
+
+        for row in 0..H {
+            for (cell, alive) in new.row_mut(row).zip(game.row(row)) {
+                *cell = alive;
+            }
+        }
+
+        new
+    }
+}
+
+/// Copies the logical state of every cell, so the new game is never
+/// [inverted](StaticGame::is_inverted).
+///
+/// # Errors
+/// Returns an error if the game isn't `W` by `H` cells.
+///
+/// # Examples
+/// ```
+/// # use cgol::{static_game::StaticGame, Game, Cell::*};
+/// # use std::convert::TryFrom;
+/// let mut game = Game::new(4, 3);
+/// game[(3, 1)] = Alive;
+///
+/// let fixed = StaticGame::<4, 3>::try_from(&game).unwrap();
+/// assert_eq!(fixed.get(1, 3), Some(Alive));
+/// assert_eq!(Game::from(&fixed), game);
+///
+/// assert!(StaticGame::<3, 4>::try_from(&game).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+impl<const W: usize, const H: usize, const WORDS: usize> TryFrom<&Game>
+    for StaticGame<W, H, WORDS>
+{
+    type Error = SizeMismatchError;
+
+    fn try_from(game: &Game) -> Result<Self, Self::Error> {
+        if game.width() != W || game.height() != H {
+            return Err(SizeMismatchError {
+                width: game.width(),
+                height: game.height(),
+            });
+        }
+
+        let mut new = StaticGame::with_rule(game.rule());
+        for row in 0..H {
+            for (col, cell) in game.row(row).enumerate() {
+                new.store(row, col, cell);
+            }
+        }
+
+        Ok(new)
+    }
+}
+/// An error which can be returned when converting a [`Game`] into a [`StaticGame`] of a
+/// different size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeMismatchError {
+    width: usize,
+    height: usize,
+}
+
+impl fmt::Display for SizeMismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "game is {}x{}, which doesn't match the static size",
+            self.width, self.height
+        )
+    }
+}